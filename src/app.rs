@@ -1,16 +1,29 @@
-use crate::cards::deck::{Card, Deck, Suite, Value};
-use crate::cards::hand::Hand;
-use color_eyre::eyre;
-use color_eyre::eyre::bail;
-
-// CONSTANTS
-pub const MAX_LIFE: u8 = 20;
-pub const HAND_SIZE: usize = 4;
-
-#[derive(Eq, PartialEq)]
+use crate::bot::greedy::GreedyBot;
+use crate::bot::hint::Hint;
+use crate::bot::risk::{FORECAST_SAMPLES, RiskForecast};
+use crate::bot::simulate::play_game;
+use crate::bot::solver::{Solution, solve};
+use crate::cards::deck::{Deck, DeckVariant, Seed};
+use crate::engine::action::Action;
+use crate::engine::engine::{Engine, Phase};
+use crate::engine::event::Event;
+use crate::engine::replay::Replay;
+use crate::engine::rule_error::RuleError;
+use crate::engine::rules::describe_rules;
+use crate::game::tutorial::{Lesson, Tutorial};
+use crate::storage::daily::{DAILY_SETTINGS, daily_seed};
+use crate::storage::history::{RunRecord, SortRunsBy};
+use crate::storage::save::SavedRun;
+use crate::ui::Theme;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use strum_macros::EnumIter;
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
 pub enum CurrentScreen {
-    Welcome,
-
     Menu,
     Exiting,
 
@@ -21,219 +34,500 @@ pub enum CurrentScreen {
 
     Won,
     Lost,
+
+    PastRuns,
+    Statistics,
+    Settings,
+    Replay,
+    Rules,
+}
+
+impl From<Phase> for CurrentScreen {
+    fn from(phase: Phase) -> Self {
+        match phase {
+            Phase::BeforeRoom => CurrentScreen::BeforeRoom,
+            Phase::ChooseCard => CurrentScreen::ChooseCard,
+            Phase::ChooseWeaponOrBareKnuckle => CurrentScreen::ChooseWeaponOrBareKnuckle,
+            Phase::Won => CurrentScreen::Won,
+            Phase::Lost => CurrentScreen::Lost,
+        }
+    }
+}
+
+/// The options in the main menu, in the order they are listed.
+#[derive(Clone, Copy, PartialEq, Eq, EnumIter, Debug)]
+pub enum MenuItem {
+    NewRun,
+    Continue,
+    DailyChallenge,
+    Tutorial,
+    PastRuns,
+    Statistics,
+    Rules,
+    Settings,
+    Quit,
+}
+
+impl Display for MenuItem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MenuItem::NewRun => write!(f, "New run"),
+            MenuItem::Continue => write!(f, "Continue"),
+            MenuItem::DailyChallenge => write!(f, "Daily challenge"),
+            MenuItem::Tutorial => write!(f, "Tutorial"),
+            MenuItem::PastRuns => write!(f, "Past runs"),
+            MenuItem::Statistics => write!(f, "Statistics"),
+            MenuItem::Rules => write!(f, "Rules"),
+            MenuItem::Settings => write!(f, "Settings"),
+            MenuItem::Quit => write!(f, "Quit"),
+        }
+    }
+}
+
+/// The options in the settings screen, in the order they are listed.
+#[derive(Clone, Copy, PartialEq, Eq, EnumIter, Debug)]
+pub enum SettingsItem {
+    Variant,
+    AllowUndo,
+}
+
+/// Options chosen by the player before starting a run.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct Settings {
+    pub variant: DeckVariant,
+    pub allow_undo: bool, // runs without undo are "ironman" runs
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            variant: DeckVariant::default(),
+            allow_undo: true,
+        }
+    }
+}
+
+/// The greedy bot's game and the best possible game, on the seed of a run that is over.
+struct Comparison {
+    bot_run: Option<Engine>,
+    solution: Solution,
 }
 
 // holds the state
 pub struct App {
     pub current_screen: CurrentScreen,
-
-    pub deck: Deck,
-    pub hand: Hand,
-    pub life: u8,
-    pub has_avoided_room: bool,
-
-    pub equipped_weapon: Option<Card>, // should only hold diamond cards
-    pub blocked_creatures: Vec<Card>,  // a stack
-    pub in_combat_with_creature: Option<Card>, // holds the creature that the user is currently attacking - used when user is choosing whether to fight with weapon, or bare-knuckle
+    pub menu_selection: MenuItem,
+    pub settings_selection: SettingsItem,
+    pub settings: Settings,
+    pub theme: Theme,
+    pub run_settings: Settings, // settings of the run being played, which may differ from `settings` for resumed or daily runs
+    pub daily: Option<NaiveDate>, // date of the daily challenge being played, if the run is one
+    pub tutorial: Option<Tutorial>, // the step of the tutorial, if the run is the tutorial
+    pub next_seed: Option<Seed>, // seed to use for the next new run - a random seed is used if there is none
+
+    pub engine: Engine, // the rules of the game are enforced by the engine - the app only decides what to display
+    undo_stack: Vec<Engine>, // snapshots of the game before each action
+    redo_stack: Vec<Engine>, // snapshots of the game that were undone
+    pub has_used_undo: bool,
+    pub show_hints: bool, // whether the bot's advice is shown during the run
+    pub hint: Option<Hint>, // the bot's advice for the position, if hints are shown
+    pub forecast: Option<RiskForecast>, // the odds of the room, shown on demand before entering it
+
+    pub bot_run: Option<Engine>, // the greedy bot's game on the same seed, played when the run ends
+    pub bot_moves: Vec<String>,
+    pub bot_moves_scroll: usize,
+    pub solution: Option<Solution>, // the best possible game on the seed, found by the solver when the run ends
+    solver: fn(DeckVariant, Seed) -> Solution, // finds the best possible game - a full search takes a while, so tests replace it
+    comparison: Option<Receiver<Comparison>>, // the comparison with the bot, while it is computed on a worker thread
+
+    pub has_saved_run: bool, // whether there is a saved run that can be continued
+    pub is_saved_run: bool, // whether the run being played is the one in the save file
+    pub past_runs: Vec<RunRecord>, // shown in the past runs screen, in the order given by `past_runs_sort`
+    pub past_runs_sort: SortRunsBy,
+    pub past_runs_scroll: usize, // the run at the top of the list is the selected run
+
+    replay_states: Vec<Engine>, // the state of the game before the first action of the replay, and after each action
+    pub replay_actions: Vec<Action>,
+    pub replay_step: usize, // number of actions applied in the state that is shown
+
+    pub rules_variant: DeckVariant, // the rules screen explains the rules of this variant
+    pub rules_scroll: usize, // the section of the rules shown at the top of the rules screen
+    screen_before_rules: CurrentScreen, // the rules screen goes back to where it was opened from
 
     pub notifications: Vec<String>, // info or alert messages to display to the user
 }
 
 impl App {
-    fn new() -> App {
+    pub fn new(settings: Settings, next_seed: Option<Seed>) -> App {
         App {
-            current_screen: CurrentScreen::Welcome, // TODO: change this to CurrentScreen::Menu
-            deck: Deck::default(),
-            hand: Hand::new(),
-            life: MAX_LIFE,
-            has_avoided_room: false,
-            equipped_weapon: None,
-            blocked_creatures: Vec::new(),
-            in_combat_with_creature: None,
+            current_screen: CurrentScreen::Menu,
+            menu_selection: MenuItem::NewRun,
+            settings_selection: SettingsItem::Variant,
+            settings,
+            theme: Theme::default(),
+            run_settings: settings,
+            daily: None,
+            tutorial: None,
+            next_seed,
+            engine: Engine::new(settings.variant, Deck::random_seed()), // replaced when a run is started
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            has_used_undo: false,
+            show_hints: false,
+            hint: None,
+            forecast: None,
+            bot_run: None,
+            bot_moves: Vec::new(),
+            bot_moves_scroll: 0,
+            solution: None,
+            solver: solve,
+            comparison: None,
+            has_saved_run: false,
+            is_saved_run: false,
+            past_runs: Vec::new(),
+            past_runs_sort: SortRunsBy::default(),
+            past_runs_scroll: 0,
+            replay_states: Vec::new(),
+            replay_actions: Vec::new(),
+            replay_step: 0,
+            rules_variant: settings.variant,
+            rules_scroll: 0,
+            screen_before_rules: CurrentScreen::Menu,
             notifications: Vec::new(),
         }
     }
 
-    /// Draws cards from the deck up to hand size.
-    /// Returns true if there are enough cards in the deck to draw from, and false otherwise.
-    pub(crate) fn draw_cards(&mut self, hand_size: usize) -> bool {
-        let number_of_cards_to_draw = hand_size - self.hand.num_cards_remaining();
+    /// Starts a new run with the current settings.
+    pub fn start_new_run(&mut self) {
+        let seed = self.next_seed.take().unwrap_or_else(Deck::random_seed);
+        self.start_run(self.settings, Engine::new(self.settings.variant, seed), None);
+    }
 
-        for _ in 0..number_of_cards_to_draw {
-            if let Some(card) = self.deck.draw_card() {
-                self.hand.add_card(card);
-            } else {
-                return false;
-            }
-        }
+    /// Starts the daily challenge of the date.
+    pub fn start_daily_run(&mut self, date: NaiveDate) {
+        let engine = Engine::new(DAILY_SETTINGS.variant, daily_seed(date));
+        self.start_run(DAILY_SETTINGS, engine, Some(date));
+    }
 
-        true
+    /// Starts the tutorial, which walks the player through a dungeon made to teach the rules.
+    pub fn start_tutorial(&mut self) {
+        let settings = Settings {
+            variant: DeckVariant::Official,
+            allow_undo: false, // the tutorial only moves forward
+        };
+        self.start_run(settings, Tutorial::start_engine(), None);
+        self.tutorial = Some(Tutorial::default());
+    }
+
+    fn start_run(&mut self, settings: Settings, engine: Engine, daily: Option<NaiveDate>) {
+        self.run_settings = settings;
+        self.daily = daily;
+        self.tutorial = None;
+        self.is_saved_run = false; // a new run leaves the saved run alone, until it is saved over it
+        self.engine = engine;
+        self.forecast = None;
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.has_used_undo = false;
+        self.bot_run = None;
+        self.solution = None;
+        self.comparison = None;
+        self.notifications.clear();
+        self.current_screen = self.engine.phase().into();
+        self.update_hint();
     }
 
-    pub fn select_card(&mut self, card_idx: usize) -> Option<Card> {
-        self.hand.remove_card(card_idx)
+    /// Continues a run that was saved.
+    pub fn resume_run(&mut self, saved_run: SavedRun) {
+        self.run_settings = saved_run.settings;
+        self.daily = saved_run.daily;
+        self.tutorial = None;
+        self.is_saved_run = true;
+        self.engine = saved_run.engine;
+        self.forecast = None;
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.has_used_undo = saved_run.has_used_undo;
+        self.bot_run = None;
+        self.solution = None;
+        self.comparison = None;
+        self.notifications = vec!["Welcome back! Your run was restored".to_string()];
+        // the screen comes from the game, not from where the run was saved, e.g. the leave run dialog
+        self.current_screen = self.engine.phase().into();
+        self.update_hint();
     }
 
-    /// Respond to the card that the user has chosen, and returns the next screen to display (if necessary):
-    /// if no screen is returned, then the next screen as defined by the state transition will be shown.
-    pub fn handle_card(&mut self, card: Card) -> color_eyre::Result<Option<CurrentScreen>> {
-        match card {
-            Card {
-                suite: Suite::Heart,
-                rank: _,
-            } => self.add_to_life(card),
-            Card {
-                suite: Suite::Diamond,
-                rank: _,
-            } => self.equip_weapon(card),
+    /// Goes back to the screen for the current phase of the run, e.g. after cancelling leaving the run.
+    pub fn return_to_run(&mut self) {
+        self.current_screen = self.engine.phase().into();
+    }
 
-            Card {
-                suite: Suite::Spade | Suite::Club,
-                rank: _,
-            } => self.fight_creature(card),
-        }
+    pub fn show_menu(&mut self) {
+        self.current_screen = CurrentScreen::Menu;
+    }
+
+    /// Moves the selection in the menu up or down, wrapping around at the ends.
+    pub fn move_menu_selection(&mut self, down: bool) {
+        self.menu_selection = move_selection(self.menu_selection, down);
+    }
+
+    /// Moves the selection in the settings up or down, wrapping around at the ends.
+    pub fn move_settings_selection(&mut self, down: bool) {
+        self.settings_selection = move_selection(self.settings_selection, down);
     }
 
-    pub fn put_back_cards(&mut self) {
-        for idx in 0..HAND_SIZE {
-            let card_num = idx + 1;
-            if let Some(card) = self.hand.remove_card(card_num) {
-                self.deck.insert_card(card);
+    /// Changes the selected setting to its next value.
+    pub fn change_selected_setting(&mut self) {
+        match self.settings_selection {
+            SettingsItem::Variant => {
+                self.settings.variant = move_selection(self.settings.variant, true)
             }
+            SettingsItem::AllowUndo => self.settings.allow_undo = !self.settings.allow_undo,
         }
     }
 
-    /// Adds points from Heart cards to player's life points, up to a maximum of 20 points
-    fn add_to_life(&mut self, card: Card) -> eyre::Result<Option<CurrentScreen>> {
-        if card.suite != Suite::Heart {
-            bail!("Only cards with Heart suite can be used to add life points!");
+    /// Applies the action to the game, displays a notification for what happened,
+    /// and moves on to the screen for the next phase of the game.
+    pub fn apply(&mut self, action: Action) -> Result<(), RuleError> {
+        let snapshot = self.engine.clone();
+        let events = self.engine.apply(action)?;
+        self.forecast = None;
+
+        if self.run_settings.allow_undo {
+            self.undo_stack.push(snapshot);
+            self.redo_stack.clear();
         }
 
-        let life_points_given_by_card: u8 = card.rank.get_value().try_into()?;
-        let life_points_addable: u8 = MAX_LIFE - self.life; // user's life cannot exceed MAX_LIFE points
-        let life_points_to_add = std::cmp::min(life_points_given_by_card, life_points_addable);
-        self.life = self.life.saturating_add(life_points_to_add);
+        for event in events {
+            if let Some(notification) = describe_event(&event) {
+                self.add_notification(notification);
+            }
+        }
+        self.compare_with_bot();
+        self.current_screen = self.engine.phase().into();
+        self.update_hint();
 
-        // display notification
-        self.notifications
-            .push(format!("You gained {life_points_to_add} life points"));
+        Ok(())
+    }
 
-        Ok(None)
+    /// Reverts the last action. Returns false if there is nothing to undo, if undo is disabled,
+    /// or if the game is over - a finished run cannot be taken back.
+    pub fn undo(&mut self) -> bool {
+        if self.engine.is_over() {
+            return false;
+        }
+        let Some(snapshot) = self.undo_stack.pop() else {
+            return false;
+        };
+
+        self.redo_stack
+            .push(std::mem::replace(&mut self.engine, snapshot));
+        self.forecast = None;
+        self.has_used_undo = true;
+        self.current_screen = self.engine.phase().into();
+        self.update_hint();
+        self.add_notification("Undid your last move".to_string());
+
+        true
     }
 
-    fn equip_weapon(&mut self, card: Card) -> eyre::Result<Option<CurrentScreen>> {
-        self.equipped_weapon = Some(card);
-        self.blocked_creatures.clear(); // reset list of blocked creatures to None
+    /// Applies the last action that was undone again. Returns false if there is nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        let Some(snapshot) = self.redo_stack.pop() else {
+            return false;
+        };
 
-        // display notification
-        self.notifications.push(format!(
-            "You equipped: {}",
-            self.equipped_weapon.as_ref().unwrap()
-        ));
+        self.undo_stack
+            .push(std::mem::replace(&mut self.engine, snapshot));
+        self.forecast = None;
+        self.compare_with_bot();
+        self.current_screen = self.engine.phase().into();
+        self.update_hint();
+        self.add_notification("Redid your last move".to_string());
 
-        Ok(None)
+        true
     }
 
-    fn fight_creature(&mut self, card: Card) -> eyre::Result<Option<CurrentScreen>> {
-        if self.equipped_weapon.is_some()
-            && self.blocked_creatures.last().map_or_else(
-                || true,
-                |last_blocked_creature| {
-                    card.rank.get_value() < last_blocked_creature.rank.get_value()
-                },
-            )
-        {
-            // TODO: remove duplicate
-            self.in_combat_with_creature = Some(card);
+    /// Shows or hides the bot's advice.
+    pub fn toggle_hints(&mut self) {
+        self.show_hints = !self.show_hints;
+        self.update_hint();
+    }
 
-            // give user a choice whether to use the weapon, or to bare-knuckle
-            Ok(Some(CurrentScreen::ChooseWeaponOrBareKnuckle))
+    /// Works out the bot's advice once for each position, rather than every time the screen is drawn.
+    fn update_hint(&mut self) {
+        // hints would make the daily challenge unfair
+        self.hint = if self.show_hints && self.daily.is_none() {
+            Hint::for_position(&self.engine)
         } else {
-            // TODO: remove duplicate
-            self.in_combat_with_creature = Some(card);
-            self.fight_creature_bare_knuckle()
+            None
+        };
+    }
+
+    /// Shows or hides the forecast of the room. Returns false if there is no room to forecast,
+    /// i.e. the room was already entered.
+    pub fn toggle_forecast(&mut self) -> bool {
+        if self.forecast.take().is_some() {
+            return true;
         }
+        self.forecast = RiskForecast::for_position(&self.engine, FORECAST_SAMPLES);
+
+        self.forecast.is_some()
     }
 
-    pub fn fight_creature_with_weapon(&mut self) -> eyre::Result<Option<CurrentScreen>> {
-        // fight with weapon
-        let weapon_strength = self.equipped_weapon.as_ref().unwrap().rank.get_value();
+    /// Moves on to the next step of the tutorial, taking the action if the step teaches one -
+    /// `None` stands for reading the explanation. Returns false if the step expects something else.
+    /// Goes back to the menu once the tutorial is finished.
+    pub fn follow_tutorial(&mut self, action: Option<Action>) -> Result<bool, RuleError> {
+        let Some(tutorial) = &self.tutorial else {
+            return Ok(false);
+        };
+        let is_expected = match tutorial.current_step().map(|step| step.lesson) {
+            Some(Lesson::Read) => action.is_none(),
+            Some(_) => action.is_some() && action == tutorial.expected_action(&self.engine),
+            None => false,
+        };
+        if !is_expected {
+            return Ok(false);
+        }
 
-        let creature = self.in_combat_with_creature.take().unwrap();
-        let creature_strength = creature.rank.get_value();
-        let damage_to_take = creature_strength.saturating_sub(weapon_strength);
+        if let Some(action) = action {
+            self.apply(action)?;
+        }
+        if let Some(tutorial) = &mut self.tutorial {
+            tutorial.next_step();
+            if tutorial.current_step().is_none() {
+                self.leave_tutorial();
+            }
+        }
 
-        // update life points
-        self.life = self.life.saturating_sub(damage_to_take as u8);
+        Ok(true)
+    }
 
-        // display notification
-        self.add_notification(format!("You took {damage_to_take} damage"));
+    /// Stops the tutorial, which is not saved nor recorded in the past runs.
+    pub fn leave_tutorial(&mut self) {
+        self.tutorial = None;
+        self.notifications.clear();
+        self.show_menu();
+    }
 
-        if self.life == 0 {
-            // if life points remaining after this encounter is 0, player has lost the game
-            Ok(Some(CurrentScreen::Lost))
-        } else {
-            // track list of creatures that were blocked
-            self.blocked_creatures.push(creature);
+    /// Once the run is over, lets the greedy bot and the solver play the same seed,
+    /// so that the scores can be compared.
+    /// Both take a while, so they run on a worker thread - see `receive_comparison`.
+    fn compare_with_bot(&mut self) {
+        // the tutorial is played the same way every time, there is nothing to compare it with
+        if !self.engine.is_over()
+            || self.bot_run.is_some()
+            || self.is_comparing()
+            || self.tutorial.is_some()
+        {
+            return;
+        }
+        let variant = self.engine.deck().variant();
+        let seed = self.engine.seed();
+
+        let solver = self.solver;
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let comparison = Comparison {
+                solution: solver(variant, seed),
+                bot_run: play_game(&mut GreedyBot, variant, seed).ok(),
+            };
+            // nobody is waiting for the comparison any more if another run was started
+            let _ = sender.send(comparison);
+        });
+        self.comparison = Some(receiver);
+    }
 
-            Ok(None)
+    /// Whether the comparison with the bot is still being computed.
+    pub fn is_comparing(&self) -> bool {
+        self.comparison.is_some()
+    }
+
+    /// Shows the comparison with the bot once the worker thread has computed it.
+    /// Returns true if it was received.
+    pub fn receive_comparison(&mut self) -> bool {
+        let Some(receiver) = &self.comparison else {
+            return false;
+        };
+        let comparison = match receiver.try_recv() {
+            Ok(comparison) => comparison,
+            Err(TryRecvError::Empty) => return false,
+            Err(TryRecvError::Disconnected) => {
+                // the worker thread stopped without a result, there is nothing to compare with
+                self.comparison = None;
+                return false;
+            }
+        };
+        self.comparison = None;
+
+        self.solution = Some(comparison.solution);
+        if let Some(bot_run) = comparison.bot_run {
+            self.bot_moves = bot_run.replay().describe_moves().unwrap_or_default();
+            self.bot_moves_scroll = 0;
+            self.bot_run = Some(bot_run);
         }
+        true
     }
 
-    pub fn fight_creature_bare_knuckle(&mut self) -> eyre::Result<Option<CurrentScreen>> {
-        // no suitable weapon equipped, only choice is to bare-knuckle:
-        let creature = self.in_combat_with_creature.take().unwrap();
-        let damage_to_take = creature.rank.get_value() as u8;
+    /// Shows the past runs screen.
+    pub fn show_past_runs(&mut self, past_runs: Vec<RunRecord>) {
+        self.past_runs = past_runs;
+        self.past_runs_sort.sort(&mut self.past_runs);
+        self.past_runs_scroll = 0;
+        self.notifications.clear();
+        self.current_screen = CurrentScreen::PastRuns;
+    }
 
-        // subtract life points
-        self.life = self.life.saturating_sub(damage_to_take);
+    /// Lists the past runs in the next sort order.
+    pub fn sort_past_runs(&mut self) {
+        self.past_runs_sort = self.past_runs_sort.next();
+        self.past_runs_sort.sort(&mut self.past_runs);
+        self.past_runs_scroll = 0;
+    }
 
-        // display notification
-        self.add_notification(format!("You took {damage_to_take} damage"));
+    /// Shows the replay screen, at the start of the replay.
+    /// Returns an error if the replay has actions that the rules do not allow.
+    pub fn show_replay(&mut self, replay: Replay) -> Result<(), RuleError> {
+        self.replay_states = replay.play()?;
+        self.replay_actions = replay.actions;
+        self.replay_step = 0;
+        self.current_screen = CurrentScreen::Replay;
 
-        if self.life == 0 {
-            // if life points remaining after this encounter is 0, player has lost the game
-            Ok(Some(CurrentScreen::Lost))
-        } else {
-            Ok(None)
-        }
+        Ok(())
     }
 
-    // used to calculate score at the end of the game
-    pub fn calculate_score(&self) -> i32 {
-        if self.life > 0 {
-            let score = if self.hand.num_cards_remaining() == 1 {
-                let bonus_score = self
-                    .hand
-                    .iter()
-                    .filter_map(|slot| slot.as_ref())
-                    .find(|card| card.suite == Suite::Heart)
-                    .map_or_else(|| 0, |card| card.rank.get_value());
+    /// Moves the replay to the given step, which is clamped to the steps of the replay.
+    pub fn go_to_replay_step(&mut self, step: usize) {
+        self.replay_step = step.min(self.replay_actions.len());
+    }
 
-                self.life + (bonus_score as u8)
-            } else {
-                self.life
-            };
+    /// Returns the state of the game at the current step of the replay.
+    pub fn replay_state(&self) -> Option<&Engine> {
+        self.replay_states.get(self.replay_step)
+    }
+
+    /// Shows the rules of the variant, from the top.
+    pub fn show_rules(&mut self, variant: DeckVariant) {
+        self.rules_variant = variant;
+        self.rules_scroll = 0;
+        self.screen_before_rules = self.current_screen;
+        self.current_screen = CurrentScreen::Rules;
+    }
 
-            score as i32
+    /// Scrolls the rules up or down by a section, stopping at the last section.
+    pub fn scroll_rules(&mut self, down: bool) {
+        let num_sections = describe_rules(self.rules_variant).len();
+        self.rules_scroll = if down {
+            (self.rules_scroll + 1).min(num_sections.saturating_sub(1))
         } else {
-            let total_strength_of_monsters_left_in_deck = self
-                .deck
-                .iter()
-                .map(|card| {
-                    match card {
-                        // if the card is a monster, add its strength
-                        Card {
-                            suite: Suite::Spade | Suite::Club,
-                            rank: _,
-                        } => card.rank.get_value() as i32,
-                        _ => 0,
-                    }
-                })
-                .fold(0, |total, elem| total + elem);
+            self.rules_scroll.saturating_sub(1)
+        };
+    }
 
-            -total_strength_of_monsters_left_in_deck
-        }
+    /// Goes back to the screen that the rules were opened from.
+    pub fn close_rules(&mut self) {
+        self.current_screen = self.screen_before_rules;
     }
 
     fn add_notification(&mut self, notification: String) {
@@ -243,6 +537,125 @@ impl App {
 
 impl Default for App {
     fn default() -> Self {
-        App::new()
+        App::new(Settings::default(), None)
+    }
+}
+
+/// Returns the item before or after `item`, wrapping around at the ends.
+fn move_selection<T: strum::IntoEnumIterator + PartialEq>(item: T, down: bool) -> T {
+    let items: Vec<T> = T::iter().collect();
+    let idx = items.iter().position(|other| *other == item).unwrap_or(0);
+    let next_idx = if down {
+        (idx + 1) % items.len()
+    } else {
+        (idx + items.len() - 1) % items.len()
+    };
+
+    items.into_iter().nth(next_idx).unwrap_or(item)
+}
+
+/// Returns the message to display to the user for an event, if any.
+fn describe_event(event: &Event) -> Option<String> {
+    match event {
+        Event::RoomAvoided => {
+            Some("You fled! The room was put at the bottom of the dungeon".to_string())
+        }
+        Event::Healed { life_gained, .. } => {
+            Some(format!("You gained {life_gained} life points"))
+        }
+        Event::PotionWasted { .. } => Some(
+            "You already used a potion in this room - this one had no effect".to_string(),
+        ),
+        Event::WeaponEquipped { weapon } => Some(format!("You equipped: {weapon}")),
+        Event::CreatureBlocked { damage_taken, .. }
+        | Event::CreatureFoughtBareKnuckle { damage_taken, .. } => {
+            Some(format!("You took {damage_taken} damage"))
+        }
+        Event::RoomDealt
+        | Event::RoomEntered
+        | Event::CombatStarted { .. }
+        | Event::GameWon { .. }
+        | Event::GameLost { .. } => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::{App, Settings};
+    use crate::bot::solver::Solution;
+    use crate::cards::deck::{DeckVariant, Seed};
+    use crate::engine::action::Action;
+
+    /// Stands in for the solver, whose full search is too slow for tests.
+    fn no_solution(_: DeckVariant, _: Seed) -> Solution {
+        Solution {
+            score: 0,
+            actions: Vec::new(),
+            positions_searched: 0,
+        }
+    }
+
+    #[test]
+    fn undo_should_restore_the_game_before_the_action() {
+        let mut app = App::new(Settings::default(), Some(42));
+        app.start_new_run();
+        app.apply(Action::AvoidRoom).unwrap();
+        let hand_after_avoiding = app.engine.hand().to_string();
+
+        assert!(app.undo());
+        assert!(app.engine.legal_actions().contains(&Action::AvoidRoom));
+        assert!(app.has_used_undo);
+
+        assert!(app.redo());
+        assert_eq!(app.engine.hand().to_string(), hand_after_avoiding);
+        assert!(!app.redo());
+    }
+
+    #[test]
+    fn end_of_run_should_be_compared_with_the_bot() {
+        let mut app = App::new(Settings::default(), Some(42));
+        app.solver = no_solution;
+        app.start_new_run();
+        while let Some(action) = app.engine.legal_actions().first() {
+            app.apply(*action).unwrap();
+        }
+
+        // the comparison is computed on a worker thread, while the end screen is shown
+        assert!(app.is_comparing());
+        while !app.receive_comparison() {
+            assert!(app.is_comparing());
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert!(!app.is_comparing());
+        assert_eq!(app.bot_run.as_ref().unwrap().seed(), 42);
+        assert_eq!(app.solution.map(|solution| solution.score), Some(0));
+    }
+
+    #[test]
+    fn undo_should_not_be_possible_once_the_game_is_over() {
+        let mut app = App::new(Settings::default(), Some(42));
+        app.solver = no_solution;
+        app.start_new_run();
+        while let Some(action) = app.engine.legal_actions().first() {
+            app.apply(*action).unwrap();
+        }
+
+        assert!(app.engine.is_over());
+        assert!(!app.undo());
+        assert!(!app.has_used_undo);
+    }
+
+    #[test]
+    fn undo_should_not_be_possible_in_ironman_runs() {
+        let settings = Settings {
+            allow_undo: false,
+            ..Settings::default()
+        };
+        let mut app = App::new(settings, Some(42));
+        app.start_new_run();
+        app.apply(Action::EnterRoom).unwrap();
+
+        assert!(!app.undo());
+        assert!(!app.has_used_undo);
     }
 }
@@ -0,0 +1,189 @@
+use crate::bot::strategy::Strategy;
+use crate::cards::classified::{ClassifiedCard, Monster};
+use crate::cards::deck::Value;
+use crate::engine::action::Action;
+use crate::engine::engine::{Engine, Phase};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+// far beyond any score, so that winning is always preferred to staying alive, and staying alive to dying
+const WON: i32 = 1000;
+const LOST: i32 = -1000;
+
+/// Number of rooms dealt from the unseen cards to judge fleeing, as the room dealt after fleeing is not known.
+const FLEE_SAMPLES: i32 = 16;
+
+/// Looks ahead to the end of the room, and makes the move that leaves the player in the best position
+/// for the next room, as judged by `evaluate`. Like the player, it only knows the cards it has seen.
+pub struct GreedyBot;
+
+impl Strategy for GreedyBot {
+    fn choose_action(&mut self, engine: &Engine) -> Option<Action> {
+        // reversed, so that the first of the equally good actions is chosen
+        engine
+            .legal_actions()
+            .into_iter()
+            .rev()
+            .max_by_key(|action| evaluate_action(engine, *action))
+    }
+}
+
+/// Judges the position after the action - see `evaluate`.
+/// Fleeing deals a room from cards the player has not seen, so it is judged by the average of rooms
+/// dealt from the unseen cards in a random order, rather than by the room that the dungeon holds.
+pub fn evaluate_action(engine: &Engine, action: Action) -> i32 {
+    if action != Action::AvoidRoom {
+        return evaluate_after(engine, action);
+    }
+
+    let mut rng = StdRng::seed_from_u64(engine.seed());
+    let total: i64 = (0..FLEE_SAMPLES)
+        .map(|_| {
+            let mut sample = engine.clone();
+            sample.shuffle_unseen_cards(&mut rng);
+            evaluate_after(&sample, action) as i64
+        })
+        .sum();
+
+    (total / FLEE_SAMPLES as i64) as i32
+}
+
+fn evaluate_after(engine: &Engine, action: Action) -> i32 {
+    let mut after = engine.clone();
+    match after.apply(action) {
+        Ok(_) => evaluate(&after),
+        Err(_) => i32::MIN,
+    }
+}
+
+/// Judges how good the position is for the player, in life points: the life left, less the damage
+/// that the next room is expected to deal with the equipped weapon - see `expected_room_damage`.
+/// A room that is being played is judged by the best way to play it to the end.
+pub fn evaluate(engine: &Engine) -> i32 {
+    match engine.phase() {
+        Phase::Won => return WON + engine.calculate_score(),
+        Phase::Lost => return LOST + engine.calculate_score(),
+        // the rest of the room is played out, so judge the best way to play it
+        Phase::ChooseCard | Phase::ChooseWeaponOrBareKnuckle => {
+            return engine
+                .legal_actions()
+                .into_iter()
+                .map(|action| evaluate_action(engine, action))
+                .max()
+                .unwrap_or(i32::MIN);
+        }
+        Phase::BeforeRoom => {}
+    }
+
+    engine.life() as i32 - expected_room_damage(engine)
+}
+
+/// Returns the damage that the creature would deal if it was fought now - with the equipped weapon,
+/// if the weapon can be used on it.
+pub fn damage_from(engine: &Engine, creature: &Monster) -> usize {
+    match engine.equipped_weapon() {
+        Some(weapon) if can_block(engine, creature) => {
+            creature.get_value().saturating_sub(weapon.get_value())
+        }
+        _ => creature.get_value(),
+    }
+}
+
+/// Returns true if the equipped weapon can be used on the creature.
+pub fn can_block(engine: &Engine, creature: &Monster) -> bool {
+    engine.equipped_weapon().is_some()
+        && engine
+            .blocked_creatures()
+            .last()
+            .is_none_or(|last_blocked_creature| {
+                creature.get_value() < last_blocked_creature.get_value()
+            })
+}
+
+/// The damage that the next room is expected to deal: the damage of the card carried over to it (or less
+/// the life that it would heal, if it is a potion), plus the average damage of the cards the player had
+/// not seen for each card dealt around it - the cards dealt are not counted as they were not known.
+pub fn expected_room_damage(engine: &Engine) -> i32 {
+    let carried_card_num = engine.hand().carried_over_card_num();
+    let mut carried_card_damage = 0;
+    let mut dealt_cards = Vec::new();
+    for (idx, card) in engine.hand().iter().enumerate() {
+        match card {
+            Some(card) if carried_card_num == Some(idx + 1) => {
+                carried_card_damage = match ClassifiedCard::from(*card) {
+                    ClassifiedCard::Monster(creature) => damage_from(engine, &creature) as i32,
+                    ClassifiedCard::Potion(potion) => -(potion.get_value() as i32),
+                    ClassifiedCard::Weapon(_) => 0,
+                }
+            }
+            Some(card) => dealt_cards.push(*card),
+            None => {}
+        }
+    }
+
+    // the cards dealt could have been any of the cards that were not seen
+    let num_unseen_in_deck = engine.deck().len() - engine.deck().known_cards_at_bottom().count();
+    let unseen_cards = dealt_cards
+        .iter()
+        .chain(engine.deck().iter().take(num_unseen_in_deck));
+    let (num_unseen, unseen_damage) = unseen_cards.fold((0, 0), |(count, damage), card| {
+        match Monster::try_from(*card) {
+            Ok(creature) => (count + 1, damage + damage_from(engine, &creature)),
+            Err(_) => (count + 1, damage),
+        }
+    });
+    let expected_dealt_damage = (dealt_cards.len() * unseen_damage)
+        .checked_div(num_unseen)
+        .unwrap_or(0);
+
+    carried_card_damage + expected_dealt_damage as i32
+}
+
+#[cfg(test)]
+mod test {
+    use crate::bot::greedy::GreedyBot;
+    use crate::bot::simulate::play_game;
+    use crate::bot::strategy::FirstLegalAction;
+    use crate::bot::strategy::Strategy;
+    use crate::cards::deck::DeckVariant;
+    use crate::engine::action::Action;
+    use crate::engine::engine::{Engine, Phase};
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn greedy_bot_should_not_know_the_order_of_the_unseen_cards() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for seed in 0..10 {
+            // the same room, with the rest of the dungeon in another order
+            let mut engine = Engine::new(DeckVariant::Official, seed);
+            let mut shuffled = engine.clone();
+            shuffled.shuffle_unseen_cards(&mut rng);
+
+            // until a room is dealt from the unseen cards, the games can only differ if the bot knows them
+            loop {
+                let action = GreedyBot.choose_action(&engine);
+                assert_eq!(action, GreedyBot.choose_action(&shuffled));
+                let Some(action) = action.filter(|action| *action != Action::AvoidRoom) else {
+                    break;
+                };
+                engine.apply(action).unwrap();
+                shuffled.apply(action).unwrap();
+                if engine.phase() == Phase::BeforeRoom {
+                    break;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn greedy_bot_should_do_better_than_always_picking_the_first_card() {
+        // the scores of both bots on a few seeds - the seeds decide the dungeons, so they never change
+        for (seed, first_score, greedy_score) in [(3, -155, -6), (7, -183, 3), (10, -134, -2)] {
+            let first_game = play_game(&mut FirstLegalAction, DeckVariant::Official, seed).unwrap();
+            let greedy_game = play_game(&mut GreedyBot, DeckVariant::Official, seed).unwrap();
+            assert_eq!(first_game.calculate_score(), first_score);
+            assert_eq!(greedy_game.calculate_score(), greedy_score);
+        }
+    }
+}
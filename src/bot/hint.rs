@@ -0,0 +1,171 @@
+use crate::bot::greedy::GreedyBot;
+use crate::bot::strategy::Strategy;
+use crate::engine::action::Action;
+use crate::engine::engine::{Engine, Phase};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use std::fmt::{Display, Formatter};
+
+/// Number of rooms that are dealt at random to judge fleeing.
+const HINT_SAMPLES: i32 = 64;
+
+/// How an action is expected to turn out by the end of the room, if the rest of the room is played
+/// as the greedy bot would.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Outcome {
+    Dies,
+    Survives,
+    /// The life lost by the end of the room (negative if life is gained).
+    CostsLife(i32),
+    /// Fleeing deals a room from cards that the player has not seen, so only the odds are known:
+    /// the chance of dying in the room, and the life lost on average if the player survives it.
+    Odds {
+        death_percent: u32,
+        average_cost: i32,
+    },
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Dies => write!(f, "deadly"),
+            Outcome::Survives => write!(f, "survives the dungeon"),
+            Outcome::CostsLife(cost) if *cost < 0 => write!(f, "gains {} life", -cost),
+            Outcome::CostsLife(cost) => write!(f, "costs {cost} life"),
+            Outcome::Odds {
+                death_percent: 0,
+                average_cost,
+            } => write!(f, "costs {average_cost} life on average"),
+            Outcome::Odds {
+                death_percent,
+                average_cost,
+            } => write!(
+                f,
+                "{death_percent}% deadly, otherwise costs {average_cost} life on average"
+            ),
+        }
+    }
+}
+
+/// The greedy bot's advice for the position that the game is in.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Hint {
+    pub recommended: Action,
+    pub outcomes: Vec<(Action, Outcome)>, // one for each legal action
+}
+
+impl Hint {
+    /// Returns None if the game is over.
+    pub fn for_position(engine: &Engine) -> Option<Hint> {
+        let recommended = GreedyBot.choose_action(engine)?;
+        let outcomes = engine
+            .legal_actions()
+            .into_iter()
+            .filter_map(|action| {
+                // playing it out on a copy of the game would deal the room that the dungeon holds
+                if action == Action::AvoidRoom {
+                    return Some((action, odds_of_fleeing(engine)?));
+                }
+                let end_of_room = play_out_room(engine, action)?;
+                let outcome = match end_of_room.phase() {
+                    Phase::Lost => Outcome::Dies,
+                    Phase::Won => Outcome::Survives,
+                    _ => Outcome::CostsLife(engine.life() as i32 - end_of_room.life() as i32),
+                };
+                Some((action, outcome))
+            })
+            .collect();
+
+        Some(Hint {
+            recommended,
+            outcomes,
+        })
+    }
+
+    pub fn outcome_of(&self, action: Action) -> Option<Outcome> {
+        self.outcomes
+            .iter()
+            .find(|(other, _)| *other == action)
+            .map(|(_, outcome)| *outcome)
+    }
+}
+
+/// The odds of the room dealt after fleeing, from the rooms that could be dealt from the unseen cards
+/// in a random order - each played as the greedy bot would.
+fn odds_of_fleeing(engine: &Engine) -> Option<Outcome> {
+    let mut rng = StdRng::seed_from_u64(engine.seed());
+    let mut deaths = 0;
+    let mut total_cost = 0;
+    for _ in 0..HINT_SAMPLES {
+        let mut sample = engine.clone();
+        sample.shuffle_unseen_cards(&mut rng);
+        let end_of_room = play_out_room(&sample, Action::AvoidRoom)?;
+        if end_of_room.phase() == Phase::Lost {
+            deaths += 1;
+        } else {
+            total_cost += engine.life() as i32 - end_of_room.life() as i32;
+        }
+    }
+
+    Some(Outcome::Odds {
+        death_percent: (deaths as f64 / HINT_SAMPLES as f64 * 100.0).round() as u32,
+        average_cost: (total_cost as f64 / (HINT_SAMPLES - deaths).max(1) as f64).round() as i32,
+    })
+}
+
+/// Applies the action, and plays the rest of the room as the greedy bot would.
+fn play_out_room(engine: &Engine, action: Action) -> Option<Engine> {
+    let mut after = engine.clone();
+    after.apply(action).ok()?;
+
+    while matches!(
+        after.phase(),
+        Phase::ChooseCard | Phase::ChooseWeaponOrBareKnuckle
+    ) {
+        let action = GreedyBot.choose_action(&after)?;
+        after.apply(action).ok()?;
+    }
+
+    Some(after)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::bot::greedy::GreedyBot;
+    use crate::bot::hint::{Hint, Outcome};
+    use crate::bot::strategy::Strategy;
+    use crate::cards::deck::DeckVariant;
+    use crate::engine::action::Action;
+    use crate::engine::engine::Engine;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn hint_should_recommend_the_greedy_bots_move() {
+        let mut engine = Engine::new(DeckVariant::Official, 42);
+        engine.apply(Action::EnterRoom).unwrap();
+
+        let hint = Hint::for_position(&engine).unwrap();
+        assert_eq!(Some(hint.recommended), GreedyBot.choose_action(&engine));
+        assert_eq!(hint.outcomes.len(), engine.legal_actions().len());
+        assert!(hint.outcome_of(Action::EnterRoom).is_none());
+    }
+
+    #[test]
+    fn hint_should_not_know_the_order_of_the_unseen_cards() {
+        let engine = Engine::new(DeckVariant::Official, 42);
+        let mut shuffled = engine.clone();
+        shuffled.shuffle_unseen_cards(&mut StdRng::seed_from_u64(7));
+        assert_ne!(
+            shuffled.deck().iter().collect::<Vec<_>>(),
+            engine.deck().iter().collect::<Vec<_>>()
+        );
+
+        let hint = Hint::for_position(&engine).unwrap();
+        assert!(matches!(
+            hint.outcome_of(Action::AvoidRoom),
+            Some(Outcome::Odds { .. })
+        ));
+        assert_eq!(Hint::for_position(&shuffled), Some(hint));
+    }
+}
@@ -0,0 +1,8 @@
+//! Players that are not human - they choose their moves with a [`strategy::Strategy`], and play
+//! against the engine without a terminal.
+pub mod greedy;
+pub mod hint;
+pub mod risk;
+pub mod simulate;
+pub mod solver;
+pub mod strategy;
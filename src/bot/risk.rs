@@ -0,0 +1,248 @@
+use crate::cards::classified::ClassifiedCard;
+use crate::cards::deck::{Card, Value};
+use crate::engine::engine::{Engine, HAND_SIZE, MAX_LIFE, Phase};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use std::collections::BTreeMap;
+
+/// Number of rooms that are dealt at random to forecast the room after fleeing.
+pub const FORECAST_SAMPLES: usize = 10_000;
+
+/// How a room turns out when it is played in the way that loses the least life.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RoomOutcome {
+    Dies,
+    /// The life lost by the end of the room (negative if life is gained).
+    LosesLife(i32),
+}
+
+/// The odds of the rooms ahead, before deciding whether to enter the room or to flee from it.
+#[derive(Clone, PartialEq, Debug)]
+pub struct RiskForecast {
+    /// The room that was dealt, which is known.
+    pub this_room: RoomOutcome,
+    /// Number of the sampled rooms after fleeing, by the life that they cost.
+    pub life_lost_counts: BTreeMap<i32, usize>,
+    pub deaths: usize,
+    pub samples: usize,
+}
+
+impl RiskForecast {
+    /// Forecasts the room that was dealt, and the room that would be dealt after fleeing from it -
+    /// which is drawn from the cards in the dungeon that the player has not seen yet.
+    /// Returns None unless the player is deciding whether to enter the room.
+    pub fn for_position(engine: &Engine, samples: usize) -> Option<RiskForecast> {
+        if engine.phase() != Phase::BeforeRoom {
+            return None;
+        }
+        let fighter = Fighter::of(engine);
+        let room: Vec<Card> = engine.hand().iter().flatten().copied().collect();
+
+        // fleeing puts the room under the cards that are known to be at the bottom, and the new room
+        // is drawn from the top - the cards above the known ones are in an order the player cannot know
+        let num_known_at_bottom = engine.deck().known_cards_at_bottom().count();
+        let mut unknown: Vec<Card> = engine
+            .deck()
+            .iter()
+            .take(engine.deck().len() - num_known_at_bottom)
+            .copied()
+            .collect();
+        // sorted, so that the sampled rooms do not depend on the order of the cards either
+        unknown.sort_by_key(|card| (card.suite as u8, card.rank as u8));
+        let known_at_bottom: Vec<Card> = engine
+            .deck()
+            .known_cards_at_bottom()
+            .chain(room.iter())
+            .copied()
+            .collect();
+
+        let mut rng = StdRng::seed_from_u64(engine.seed());
+        let mut life_lost_counts = BTreeMap::new();
+        let mut deaths = 0;
+        for _ in 0..samples {
+            unknown.shuffle(&mut rng);
+            let next_room: Vec<Card> = unknown
+                .iter()
+                .chain(known_at_bottom.iter())
+                .take(HAND_SIZE)
+                .copied()
+                .collect();
+            match fighter.best_play(&next_room) {
+                RoomOutcome::Dies => deaths += 1,
+                RoomOutcome::LosesLife(life_lost) => {
+                    *life_lost_counts.entry(life_lost).or_insert(0) += 1
+                }
+            }
+        }
+
+        Some(RiskForecast {
+            this_room: fighter.best_play(&room),
+            life_lost_counts,
+            deaths,
+            samples,
+        })
+    }
+
+    pub fn death_chance(&self) -> f64 {
+        self.deaths as f64 / self.samples as f64
+    }
+
+    /// The average life lost in the sampled rooms that the player survives.
+    pub fn average_life_lost(&self) -> f64 {
+        let survived = self.samples - self.deaths;
+        let total: i64 = self
+            .life_lost_counts
+            .iter()
+            .map(|(life_lost, count)| *life_lost as i64 * *count as i64)
+            .sum();
+
+        total as f64 / survived.max(1) as f64
+    }
+
+    /// The chance of losing life in each range of `bucket_size` points (e.g. 0 to 4, 5 to 9...),
+    /// by the lowest life lost in the range - rooms that heal are counted in the ranges below 0.
+    pub fn life_lost_distribution(&self, bucket_size: i32) -> Vec<(i32, f64)> {
+        let mut buckets: BTreeMap<i32, usize> = BTreeMap::new();
+        for (life_lost, count) in &self.life_lost_counts {
+            *buckets
+                .entry(life_lost.div_euclid(bucket_size) * bucket_size)
+                .or_insert(0) += count;
+        }
+
+        buckets
+            .into_iter()
+            .map(|(bucket, count)| (bucket, count as f64 / self.samples as f64))
+            .collect()
+    }
+}
+
+/// The state of the player that decides how a room can be played.
+#[derive(Clone, Copy, Debug)]
+struct Fighter {
+    life: i32,
+    weapon: Option<usize>,
+    last_blocked: Option<usize>, // the weapon can only be used on creatures weaker than this one
+    has_used_potion: bool,
+}
+
+impl Fighter {
+    fn of(engine: &Engine) -> Fighter {
+        Fighter {
+            life: engine.life() as i32,
+            weapon: engine.equipped_weapon().map(|weapon| weapon.get_value()),
+            last_blocked: engine
+                .blocked_creatures()
+                .last()
+                .map(|creature| creature.get_value()),
+            has_used_potion: engine.potions_used_in_room() > 0,
+        }
+    }
+
+    /// Plays the room in every order, and every way of fighting, to find the way that loses the least life.
+    /// All but one card of the room are played, as the last one is carried over to the next room.
+    fn best_play(&self, room: &[Card]) -> RoomOutcome {
+        let cards_to_play = room.len().saturating_sub(1);
+        match self.most_life_left(room, cards_to_play) {
+            Some(life) => RoomOutcome::LosesLife(self.life - life),
+            None => RoomOutcome::Dies,
+        }
+    }
+
+    /// Returns None if the player dies whichever way the cards are played.
+    fn most_life_left(&self, cards: &[Card], cards_to_play: usize) -> Option<i32> {
+        if cards_to_play == 0 {
+            return Some(self.life);
+        }
+
+        let mut best = None;
+        for (idx, card) in cards.iter().enumerate() {
+            let rest: Vec<Card> = cards
+                .iter()
+                .enumerate()
+                .filter(|(other_idx, _)| *other_idx != idx)
+                .map(|(_, card)| *card)
+                .collect();
+
+            for after in self.play(*card) {
+                if after.life <= 0 {
+                    continue;
+                }
+                best = best.max(after.most_life_left(&rest, cards_to_play - 1));
+            }
+        }
+
+        best
+    }
+
+    /// Returns the states that the player can be in after playing the card - a creature can be fought
+    /// bare-knuckle, or with the weapon if it can be used on it.
+    fn play(&self, card: Card) -> Vec<Fighter> {
+        match ClassifiedCard::from(card) {
+            ClassifiedCard::Potion(potion) => {
+                let life = if self.has_used_potion {
+                    self.life
+                } else {
+                    (self.life + potion.get_value() as i32).min(MAX_LIFE as i32)
+                };
+                vec![Fighter {
+                    life,
+                    has_used_potion: true,
+                    ..*self
+                }]
+            }
+            ClassifiedCard::Weapon(weapon) => vec![Fighter {
+                weapon: Some(weapon.get_value()),
+                last_blocked: None,
+                ..*self
+            }],
+            ClassifiedCard::Monster(creature) => {
+                let strength = creature.get_value();
+                let bare_knuckle = Fighter {
+                    life: self.life - strength as i32,
+                    ..*self
+                };
+
+                match self.weapon {
+                    Some(weapon) if self.last_blocked.is_none_or(|last| strength < last) => vec![
+                        bare_knuckle,
+                        Fighter {
+                            life: self.life - strength.saturating_sub(weapon) as i32,
+                            last_blocked: Some(strength),
+                            ..*self
+                        },
+                    ],
+                    _ => vec![bare_knuckle],
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::bot::hint::{Hint, Outcome};
+    use crate::bot::risk::{RiskForecast, RoomOutcome};
+    use crate::cards::deck::DeckVariant;
+    use crate::engine::action::Action;
+    use crate::engine::engine::Engine;
+
+    #[test]
+    fn forecast_should_not_lose_more_life_than_the_greedy_bot() {
+        for seed in 0..20 {
+            let engine = Engine::new(DeckVariant::Official, seed);
+            let forecast = RiskForecast::for_position(&engine, 100).unwrap();
+            let greedy = Hint::for_position(&engine)
+                .unwrap()
+                .outcome_of(Action::EnterRoom)
+                .unwrap();
+
+            assert_eq!(forecast.life_lost_counts.values().sum::<usize>() + forecast.deaths, 100);
+            match (forecast.this_room, greedy) {
+                (RoomOutcome::LosesLife(best), Outcome::CostsLife(greedy)) => assert!(best <= greedy),
+                (RoomOutcome::Dies, greedy) => assert_eq!(greedy, Outcome::Dies),
+                _ => {}
+            }
+        }
+    }
+}
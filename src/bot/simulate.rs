@@ -0,0 +1,174 @@
+use crate::bot::strategy::{Strategy, StrategyKind};
+use crate::cards::deck::{DeckVariant, Seed};
+use crate::engine::engine::{Engine, Phase};
+use crate::engine::rule_error::RuleError;
+use std::fmt::{Display, Formatter};
+
+const SCORE_BUCKET_SIZE: i32 = 10; // width of the bars of the score distribution
+const MAX_BAR_LENGTH: usize = 50;
+
+/// Plays a game to the end with the strategy.
+/// Returns an error if the strategy chooses an action that is not allowed.
+pub fn play_game(
+    strategy: &mut dyn Strategy,
+    variant: DeckVariant,
+    seed: Seed,
+) -> Result<Engine, RuleError> {
+    let mut engine = Engine::new(variant, seed);
+    while let Some(action) = strategy.choose_action(&engine) {
+        engine.apply(action)?;
+    }
+
+    Ok(engine)
+}
+
+/// The results of playing many games with the same strategy.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SimulationReport {
+    pub strategy: StrategyKind,
+    pub variant: DeckVariant,
+    pub first_seed: Seed,
+    pub games: usize,
+    pub wins: usize,
+    pub scores: Vec<i32>,
+    pub total_rooms_cleared: u32,
+}
+
+/// Plays `games` games with the strategy, with the seeds `first_seed`, `first_seed + 1`, etc.
+pub fn simulate(
+    strategy: StrategyKind,
+    variant: DeckVariant,
+    games: usize,
+    first_seed: Seed,
+) -> Result<SimulationReport, RuleError> {
+    let mut report = SimulationReport {
+        strategy,
+        variant,
+        first_seed,
+        games,
+        wins: 0,
+        scores: Vec::with_capacity(games),
+        total_rooms_cleared: 0,
+    };
+
+    for seed in (first_seed..).take(games) {
+        let engine = play_game(strategy.build(seed).as_mut(), variant, seed)?;
+
+        if engine.phase() == Phase::Won {
+            report.wins += 1;
+        }
+        report.scores.push(engine.calculate_score());
+        report.total_rooms_cleared += engine.stats().rooms_cleared;
+    }
+
+    Ok(report)
+}
+
+impl SimulationReport {
+    pub fn win_rate(&self) -> f64 {
+        self.wins as f64 / self.games as f64
+    }
+
+    pub fn average_score(&self) -> f64 {
+        self.scores.iter().sum::<i32>() as f64 / self.games as f64
+    }
+
+    pub fn average_rooms_cleared(&self) -> f64 {
+        self.total_rooms_cleared as f64 / self.games as f64
+    }
+
+    /// Returns the number of games per range of scores, from the lowest to the highest scores.
+    /// Each range starts at a multiple of `SCORE_BUCKET_SIZE`.
+    pub fn score_distribution(&self) -> Vec<(i32, usize)> {
+        let (Some(min), Some(max)) = (self.scores.iter().min(), self.scores.iter().max()) else {
+            return Vec::new();
+        };
+
+        (min.div_euclid(SCORE_BUCKET_SIZE)..=max.div_euclid(SCORE_BUCKET_SIZE))
+            .map(|bucket| {
+                let games = self
+                    .scores
+                    .iter()
+                    .filter(|score| score.div_euclid(SCORE_BUCKET_SIZE) == bucket)
+                    .count();
+                (bucket * SCORE_BUCKET_SIZE, games)
+            })
+            .collect()
+    }
+}
+
+impl Display for SimulationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Strategy: {}, deck: {}, games: {} (seeds {} to {})",
+            self.strategy,
+            self.variant,
+            self.games,
+            self.first_seed,
+            self.first_seed + self.games.saturating_sub(1) as Seed
+        )?;
+        if self.games == 0 {
+            return Ok(());
+        }
+
+        writeln!(
+            f,
+            "Win rate: {:.1}% ({} of {})",
+            self.win_rate() * 100.0,
+            self.wins,
+            self.games
+        )?;
+        writeln!(
+            f,
+            "Score: min {}, average {:.1}, max {}",
+            self.scores.iter().min().unwrap_or(&0),
+            self.average_score(),
+            self.scores.iter().max().unwrap_or(&0)
+        )?;
+        writeln!(f, "Average rooms cleared: {:.1}", self.average_rooms_cleared())?;
+
+        writeln!(f, "Score distribution:")?;
+        let distribution = self.score_distribution();
+        let most_games = distribution.iter().map(|(_, games)| *games).max().unwrap_or(1);
+        for (lowest_score, games) in distribution {
+            let bar_length = (games * MAX_BAR_LENGTH).div_ceil(most_games);
+            writeln!(
+                f,
+                "{:>5} to {:>4} | {} {}",
+                lowest_score,
+                lowest_score + SCORE_BUCKET_SIZE - 1,
+                "#".repeat(bar_length),
+                games
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::bot::simulate::simulate;
+    use crate::bot::strategy::StrategyKind;
+    use crate::cards::deck::DeckVariant;
+
+    #[test]
+    fn simulation_should_be_reproducible() {
+        let report = simulate(StrategyKind::Random, DeckVariant::Official, 50, 100).unwrap();
+
+        assert_eq!(report.scores.len(), 50);
+        assert_eq!(
+            report
+                .score_distribution()
+                .iter()
+                .map(|(_, games)| games)
+                .sum::<usize>(),
+            50
+        );
+        assert_eq!(
+            report,
+            simulate(StrategyKind::Random, DeckVariant::Official, 50, 100).unwrap()
+        );
+    }
+}
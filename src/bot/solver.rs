@@ -0,0 +1,427 @@
+use crate::bot::greedy::GreedyBot;
+use crate::bot::strategy::Strategy;
+use crate::cards::classified::{ClassifiedCard, Monster};
+use crate::cards::deck::{Card, DeckVariant, Seed, Value};
+use crate::engine::action::Action;
+use crate::engine::engine::{Engine, HAND_SIZE, MAX_LIFE, MAX_POTIONS_PER_ROOM, Phase};
+use std::collections::HashMap;
+
+/// The best possible game for a seed.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Solution {
+    pub score: i32,
+    pub actions: Vec<Action>, // the moves that lead to the score
+    pub positions_searched: usize,
+}
+
+/// Finds the best possible score for the seed - as the order of the deck is decided by the seed,
+/// every move can be tried on the real dungeon, and the best game picked.
+pub fn solve(variant: DeckVariant, seed: Seed) -> Solution {
+    solve_from(&Engine::new(variant, seed))
+}
+
+/// Finds the best possible score from the position that the game is in. The score is narrowed down from
+/// between the greedy bot's score and the upper bound, by searching for a game that reaches the score
+/// halfway between them - searches that only have to reach a score can skip far more positions.
+pub fn solve_from(engine: &Engine) -> Solution {
+    let mut greedy_game = engine.clone();
+    while let Some(action) = GreedyBot.choose_action(&greedy_game) {
+        if greedy_game.apply(action).is_err() {
+            break;
+        }
+    }
+    let greedy_score = if greedy_game.is_over() {
+        greedy_game.calculate_score()
+    } else {
+        i32::MIN
+    };
+
+    let mut solver = Solver::new();
+    let mut best_game = (greedy_score > 0).then_some(greedy_game.clone());
+    let mut lowest = greedy_score.max(0); // a game scores this much
+    let mut highest = upper_bound(engine); // no game scores more
+    while lowest < highest {
+        let target = (lowest + highest + 1) / 2;
+        solver.floor = target - 1;
+        solver.ceiling = target;
+        let most = solver.search(engine, highest);
+        match solver.best.take() {
+            Some(game) => {
+                lowest = game.calculate_score();
+                best_game = Some(game);
+            }
+            None => highest = most.min(target - 1),
+        }
+    }
+    if let Some(game) = best_game {
+        return Solution::new(engine, &game, solver.positions_searched);
+    }
+
+    // the player cannot survive, so look for the game where the player dies with the fewest creatures left
+    solver.floor = greedy_score;
+    solver.ceiling = 0;
+    solver.search(engine, upper_bound(engine));
+    let game = solver
+        .best
+        .take()
+        .or(greedy_game.is_over().then_some(greedy_game));
+    Solution::new(
+        engine,
+        &game.unwrap_or_else(|| engine.clone()),
+        solver.positions_searched,
+    )
+}
+
+impl Solution {
+    fn new(start: &Engine, end: &Engine, positions_searched: usize) -> Solution {
+        Solution {
+            score: end.calculate_score(),
+            // only the moves made from the given position
+            actions: end.replay().actions[start.replay().actions.len()..].to_vec(),
+            positions_searched,
+        }
+    }
+}
+
+/// Searches every move depth-first for a game that scores more than `floor`, skipping the positions that
+/// cannot beat it. A search finds out the most that any game from the position can score, and the positions
+/// are remembered with it, so that they are not searched again.
+struct Solver {
+    floor: i32,           // the score to beat - raised to the score of the best game found
+    ceiling: i32, // the search stops once it finds a game with this score, as no game scores more
+    best: Option<Engine>, // the end of the best game found
+    positions_searched: usize,
+    searched: HashMap<PositionKey, Vec<(Resources, i32)>>, // the most that a game from the position can score
+}
+
+impl Solver {
+    fn new() -> Solver {
+        Solver {
+            floor: i32::MIN,
+            ceiling: i32::MAX,
+            best: None,
+            positions_searched: 0,
+            searched: HashMap::new(),
+        }
+    }
+
+    /// Searches the moves from the position, and returns the most that a game from the position can score.
+    /// `bound` is the `upper_bound` of its score.
+    fn search(&mut self, engine: &Engine, bound: i32) -> i32 {
+        self.positions_searched += 1;
+
+        if engine.is_over() {
+            let score = engine.calculate_score();
+            if score > self.floor {
+                self.floor = score;
+                self.best = Some(engine.clone());
+            }
+            return score;
+        }
+        if bound <= self.floor {
+            return bound;
+        }
+
+        let key = PositionKey::of(engine);
+        let resources = Resources::of(engine);
+        let bound = bound.min(self.most_known(&key, &resources));
+        if bound <= self.floor {
+            return bound;
+        }
+
+        // the most promising moves first
+        let mut next_positions: Vec<(i32, Engine)> = engine
+            .legal_actions()
+            .into_iter()
+            .filter_map(|action| {
+                let mut next = engine.clone();
+                next.apply(action).ok()?;
+                Some((upper_bound(&next), next))
+            })
+            .collect();
+        next_positions.sort_by_key(|(bound, next)| (-bound, -(next.life() as i32)));
+
+        let mut most = i32::MIN;
+        for (next_bound, next) in next_positions {
+            most = most.max(if self.floor < self.ceiling {
+                self.search(&next, next_bound)
+            } else {
+                next_bound
+            });
+        }
+        let most = most.min(bound);
+
+        self.remember(key, resources, most);
+        most
+    }
+
+    /// Returns the least of the most that a game can score from the position, as found by the searches of the
+    /// position with at least the same resources.
+    fn most_known(&self, key: &PositionKey, resources: &Resources) -> i32 {
+        self.searched.get(key).map_or(i32::MAX, |searched| {
+            searched
+                .iter()
+                .filter(|(other, _)| other.at_least_as_good_as(resources))
+                .map(|(_, most)| *most)
+                .min()
+                .unwrap_or(i32::MAX)
+        })
+    }
+
+    fn remember(&mut self, key: PositionKey, resources: Resources, most: i32) {
+        let searched = self.searched.entry(key).or_default();
+        // forget the searches that this one tells more than
+        searched.retain(|(other, other_most)| {
+            !(resources.at_least_as_good_as(other) && most <= *other_most)
+        });
+        searched.push((resources, most));
+    }
+}
+
+/// Everything about a position that decides the rest of the game, except the player's resources:
+/// the phase, the creature being fought, whether a potion was used in the room, the cards in the room,
+/// and the order of the deck. Cards are told apart by what they are used as and their value, as two
+/// creatures of the same strength play the same.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+struct PositionKey(Vec<u8>);
+
+impl PositionKey {
+    fn of(engine: &Engine) -> PositionKey {
+        let mut key = vec![
+            engine.phase() as u8,
+            engine.potions_used_in_room(),
+            engine
+                .in_combat_with_creature()
+                .map_or(0, |creature| card_code(&(*creature).into())),
+        ];
+        let mut room: Vec<u8> = engine
+            .hand()
+            .iter()
+            .map(|slot| slot.as_ref().map_or(0, card_code))
+            .collect();
+        // the order of the room only matters if it can be avoided, as it then goes to the bottom of
+        // the deck in the order of its slots
+        if engine.phase() != Phase::BeforeRoom {
+            room.sort_unstable();
+        }
+        key.extend(room);
+        key.push(u8::MAX); // separates the room from the deck
+        key.extend(engine.deck().iter().map(card_code));
+
+        PositionKey(key)
+    }
+}
+
+fn card_code(card: &Card) -> u8 {
+    let kind = match ClassifiedCard::from(*card) {
+        ClassifiedCard::Monster(_) => 1,
+        ClassifiedCard::Weapon(_) => 2,
+        ClassifiedCard::Potion(_) => 3,
+    };
+    kind * 16 + card.rank.get_value() as u8
+}
+
+/// What the player has to get through the rest of the dungeon with.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct Resources {
+    life: u8,
+    weapon: usize,
+    strongest_blockable: usize, // the strongest creature that the weapon can be used on
+}
+
+impl Resources {
+    /// A weapon's strength only matters up to the strength of the creatures left that it can be used on,
+    /// so it is counted up to that - positions that only differ beyond it play the same.
+    fn of(engine: &Engine) -> Resources {
+        let strongest_creature = engine
+            .hand()
+            .iter()
+            .flatten()
+            .chain(engine.deck().iter())
+            .filter_map(|card| Monster::try_from(*card).ok())
+            .chain(engine.in_combat_with_creature().copied())
+            .map(|creature| creature.get_value())
+            .max()
+            .unwrap_or(0);
+        let strongest_blockable = match engine.blocked_creatures().last() {
+            Some(creature) => creature.get_value() - 1,
+            None => usize::MAX,
+        }
+        .min(strongest_creature);
+        let weapon = engine
+            .equipped_weapon()
+            .map_or(0, |weapon| weapon.get_value().min(strongest_blockable));
+
+        Resources {
+            life: engine.life(),
+            weapon,
+            // without a weapon, no creature can be blocked
+            strongest_blockable: if weapon == 0 { 0 } else { strongest_blockable },
+        }
+    }
+
+    /// More life, and a weapon that is at least as strong and can be used on at least the same
+    /// creatures, can only lead to the same or a better score.
+    fn at_least_as_good_as(&self, other: &Resources) -> bool {
+        self.life >= other.life
+            && self.weapon >= other.weapon
+            && self.strongest_blockable >= other.strongest_blockable
+    }
+}
+
+/// The highest score that could still be reached, or 0 if the player cannot survive.
+///
+/// Every creature left has to be fought, except for the last card of the dungeon, which is never used.
+/// A weapon can only be used on creatures weaker than the last one it blocked, so it blocks at most one
+/// creature of each strength: the creatures of each strength are at best blocked by the strongest
+/// weapons that can still be used on them. Only one potion heals in each room, and a potion that is
+/// the last card adds to the score instead.
+fn upper_bound(engine: &Engine) -> i32 {
+    let resources = Resources::of(engine);
+    // the number of cards left of each value
+    let mut creatures = [0; 16];
+    let mut weapons = [0; 16];
+    let mut potions = [0; 16];
+    for card in engine.hand().iter().flatten().chain(engine.deck().iter()) {
+        match ClassifiedCard::from(*card) {
+            ClassifiedCard::Monster(creature) => creatures[creature.get_value()] += 1,
+            ClassifiedCard::Weapon(weapon) => weapons[weapon.get_value()] += 1,
+            ClassifiedCard::Potion(potion) => potions[potion.get_value()] += 1,
+        }
+    }
+    // strongest first
+    let sorted = |counts: [usize; 16]| {
+        let mut values = [0; 16];
+        let mut len = 0;
+        for value in (0..counts.len()).rev() {
+            for _ in 0..counts[value] {
+                values[len] = value as i32;
+                len += 1;
+            }
+        }
+        (values, len)
+    };
+    let (weapons, num_weapons) = sorted(weapons);
+    let (potions, num_potions) = sorted(potions);
+    let weapons = &weapons[..num_weapons];
+    let potions = &potions[..num_potions];
+
+    // the creature being fought can only be fought with the equipped weapon
+    let mut least_damage = engine.in_combat_with_creature().map_or(0, |creature| {
+        let strength = creature.get_value();
+        if strength <= resources.strongest_blockable {
+            strength.saturating_sub(resources.weapon)
+        } else {
+            strength
+        }
+    }) as i32;
+    // the most damage saved by leaving a creature as the last card
+    let mut most_damage_skipped = 0;
+    for (strength, count) in creatures
+        .iter()
+        .enumerate()
+        .filter(|(_, count)| **count > 0)
+    {
+        let strength = strength as i32;
+        let mut equipped_weapon =
+            (strength as usize <= resources.strongest_blockable).then_some(resources.weapon as i32);
+        let mut weapons_left = weapons.iter().copied();
+        let mut next_weapon_left = weapons_left.next();
+        for _ in 0..*count {
+            // the strongest of the weapons that are not used on another creature of the same strength
+            let weapon = match (equipped_weapon, next_weapon_left) {
+                (Some(equipped), Some(left)) if equipped < left => {
+                    next_weapon_left = weapons_left.next();
+                    left
+                }
+                (Some(equipped), _) => {
+                    equipped_weapon = None;
+                    equipped
+                }
+                (None, Some(left)) => {
+                    next_weapon_left = weapons_left.next();
+                    left
+                }
+                (None, None) => 0,
+            };
+            let damage = (strength - weapon).max(0);
+            least_damage += damage;
+            most_damage_skipped = most_damage_skipped.max(damage);
+        }
+    }
+
+    // the rooms that a potion can still heal in: this room, and one room for every 3 cards of the deck
+    let room_can_heal = engine.potions_used_in_room() < MAX_POTIONS_PER_ROOM;
+    let rooms_left = room_can_heal as usize + engine.deck().len().div_ceil(HAND_SIZE - 1);
+    let most_healing: i32 = potions.iter().take(rooms_left).sum();
+    let most_life = engine.life() as i32 + most_healing - least_damage;
+
+    // the last card is a creature, or a weapon
+    let mut best = (most_life + most_damage_skipped).clamp(0, MAX_LIFE as i32);
+    // the last card is a potion
+    for (idx, potion) in potions.iter().enumerate() {
+        // the potion does not heal, so the next strongest potion may heal instead
+        let most_life = if idx < rooms_left {
+            most_life - potion + potions.get(rooms_left).copied().unwrap_or(0)
+        } else {
+            most_life
+        };
+        if most_life > 0 {
+            best = best.max(most_life.min(MAX_LIFE as i32) + potion);
+        }
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod test {
+    use crate::bot::greedy::GreedyBot;
+    use crate::bot::solver::{solve_from, upper_bound};
+    use crate::bot::strategy::Strategy;
+    use crate::cards::deck::DeckVariant;
+    use crate::engine::engine::{Engine, Phase};
+
+    /// Tries every move, and checks that the upper bound holds in every position on the way.
+    fn best_score_by_trying_every_move(engine: &Engine) -> i32 {
+        if engine.is_over() {
+            return engine.calculate_score();
+        }
+        let best = engine
+            .legal_actions()
+            .into_iter()
+            .map(|action| {
+                let mut next = engine.clone();
+                next.apply(action).unwrap();
+                best_score_by_trying_every_move(&next)
+            })
+            .max()
+            .unwrap();
+        assert!(upper_bound(engine) >= best.max(0));
+        best
+    }
+
+    #[test]
+    fn solution_should_be_the_best_game_from_the_position() {
+        for seed in 0..10 {
+            // the greedy bot plays until a few cards are left, so that every move can be tried
+            let mut engine = Engine::new(DeckVariant::Official, seed);
+            while !(engine.phase() == Phase::BeforeRoom && engine.deck().len() <= 4) {
+                match GreedyBot.choose_action(&engine) {
+                    Some(action) => engine.apply(action).unwrap(),
+                    None => break,
+                };
+            }
+
+            let solution = solve_from(&engine);
+            assert_eq!(solution.score, best_score_by_trying_every_move(&engine));
+
+            // the moves should lead to the score
+            for action in solution.actions {
+                engine.apply(action).unwrap();
+            }
+            assert!(engine.is_over());
+            assert_eq!(engine.calculate_score(), solution.score);
+        }
+    }
+}
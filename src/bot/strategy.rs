@@ -0,0 +1,77 @@
+use crate::bot::greedy::GreedyBot;
+use crate::cards::deck::Seed;
+use crate::engine::action::Action;
+use crate::engine::engine::Engine;
+use clap::ValueEnum;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::IndexedRandom;
+use std::fmt::{Display, Formatter};
+use strum_macros::EnumIter;
+
+/// Decides the moves of a player that is not human.
+pub trait Strategy {
+    /// Chooses one of the legal actions of the game - returns None if the game is over.
+    fn choose_action(&mut self, engine: &Engine) -> Option<Action>;
+}
+
+/// The strategies that can be chosen by name, e.g. when simulating games.
+#[derive(Clone, Copy, PartialEq, Eq, EnumIter, ValueEnum, Debug)]
+pub enum StrategyKind {
+    /// Always takes the first legal action
+    #[value(name = "first")]
+    FirstLegalAction,
+    /// Takes any of the legal actions
+    Random,
+    /// Takes the action that leaves it in the best position at the end of the room
+    Greedy,
+}
+
+impl StrategyKind {
+    /// Creates the strategy - `seed` is used by strategies that make random choices.
+    pub fn build(&self, seed: Seed) -> Box<dyn Strategy> {
+        match self {
+            StrategyKind::FirstLegalAction => Box::new(FirstLegalAction),
+            StrategyKind::Random => Box::new(RandomStrategy::new(seed)),
+            StrategyKind::Greedy => Box::new(GreedyBot),
+        }
+    }
+}
+
+impl Display for StrategyKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StrategyKind::FirstLegalAction => write!(f, "first"),
+            StrategyKind::Random => write!(f, "random"),
+            StrategyKind::Greedy => write!(f, "greedy"),
+        }
+    }
+}
+
+/// Always enters the room, picks the leftmost card, and fights with the weapon when it can.
+pub struct FirstLegalAction;
+
+impl Strategy for FirstLegalAction {
+    fn choose_action(&mut self, engine: &Engine) -> Option<Action> {
+        engine.legal_actions().first().copied()
+    }
+}
+
+/// Picks any of the legal actions.
+pub struct RandomStrategy {
+    rng: StdRng,
+}
+
+impl RandomStrategy {
+    pub fn new(seed: Seed) -> Self {
+        RandomStrategy {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Strategy for RandomStrategy {
+    fn choose_action(&mut self, engine: &Engine) -> Option<Action> {
+        engine.legal_actions().choose(&mut self.rng).copied()
+    }
+}
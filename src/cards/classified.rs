@@ -0,0 +1,157 @@
+use crate::cards::deck::{Card, Suite, Value};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// What a card is used as in the dungeon, decided by its suite.
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
+pub enum CardKind {
+    Monster, // Spade and Club cards
+    Weapon,  // Diamond cards
+    Potion,  // Heart cards
+}
+
+impl CardKind {
+    pub fn of(card: &Card) -> CardKind {
+        match card.suite {
+            Suite::Spade | Suite::Club => CardKind::Monster,
+            Suite::Diamond => CardKind::Weapon,
+            Suite::Heart => CardKind::Potion,
+        }
+    }
+}
+
+impl Display for CardKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CardKind::Monster => write!(f, "monster"),
+            CardKind::Weapon => write!(f, "weapon"),
+            CardKind::Potion => write!(f, "potion"),
+        }
+    }
+}
+
+/// Returned when a card is converted to a kind of card that it is not.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct WrongCardKind {
+    pub card: Card,
+    pub expected: CardKind,
+}
+
+impl Display for WrongCardKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} is a {}, not a {}",
+            self.card,
+            CardKind::of(&self.card),
+            self.expected
+        )
+    }
+}
+
+impl std::error::Error for WrongCardKind {}
+
+/// Defines a newtype over `Card` that can only hold cards of the given kind.
+macro_rules! classified_card {
+    ($(#[$doc:meta])* $name:ident, $kind:expr) => {
+        $(#[$doc])*
+        #[derive(Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize, Debug)]
+        #[serde(try_from = "Card", into = "Card")] // so that a saved card cannot bypass the classification
+        pub struct $name(Card);
+
+        impl TryFrom<Card> for $name {
+            type Error = WrongCardKind;
+
+            fn try_from(card: Card) -> Result<Self, Self::Error> {
+                if CardKind::of(&card) == $kind {
+                    Ok($name(card))
+                } else {
+                    Err(WrongCardKind {
+                        card,
+                        expected: $kind,
+                    })
+                }
+            }
+        }
+
+        impl From<$name> for Card {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl Value for $name {
+            fn get_value(&self) -> usize {
+                self.0.rank.get_value()
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+    };
+}
+
+classified_card!(
+    /// A Spade or Club card - its value is its strength.
+    Monster,
+    CardKind::Monster
+);
+classified_card!(
+    /// A Diamond card - its value is its strength.
+    Weapon,
+    CardKind::Weapon
+);
+classified_card!(
+    /// A Heart card - its value is the number of life points it heals.
+    Potion,
+    CardKind::Potion
+);
+
+/// A card, classified by what it is used as in the dungeon.
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
+pub enum ClassifiedCard {
+    Monster(Monster),
+    Weapon(Weapon),
+    Potion(Potion),
+}
+
+impl From<Card> for ClassifiedCard {
+    fn from(card: Card) -> Self {
+        match CardKind::of(&card) {
+            CardKind::Monster => ClassifiedCard::Monster(Monster(card)),
+            CardKind::Weapon => ClassifiedCard::Weapon(Weapon(card)),
+            CardKind::Potion => ClassifiedCard::Potion(Potion(card)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cards::classified::{CardKind, Monster, Weapon, WrongCardKind};
+    use crate::cards::deck::{Card, Rank, Suite};
+
+    #[test]
+    fn only_diamonds_should_be_weapons() {
+        let heart = Card {
+            suite: Suite::Heart,
+            rank: Rank::Five,
+        };
+        assert_eq!(
+            Weapon::try_from(heart),
+            Err(WrongCardKind {
+                card: heart,
+                expected: CardKind::Weapon
+            })
+        );
+        assert!(Monster::try_from(heart).is_err());
+
+        let diamond = Card {
+            suite: Suite::Diamond,
+            rank: Rank::Five,
+        };
+        assert_eq!(Weapon::try_from(diamond).map(Card::from), Ok(diamond));
+    }
+}
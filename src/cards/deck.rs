@@ -1,69 +1,201 @@
+use clap::ValueEnum;
 use rand::prelude::*;
-use rand::rng;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::collections::vec_deque::Iter;
 use std::fmt::{Display, Formatter};
 use std::hash::Hash;
-use std::slice::Iter;
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
-pub const MAX_DECK_SIZE: usize = 52;
+/// Seed used to shuffle a deck - the same seed always produces the same run.
+pub type Seed = u64;
 
+/// The cards that make up the dungeon.
+#[derive(Clone, Copy, Default, PartialEq, Eq, EnumIter, ValueEnum, Serialize, Deserialize, Debug)]
+pub enum DeckVariant {
+    /// The published rules: the red face cards and red aces are removed, leaving 44 cards.
+    #[default]
+    Official,
+    /// All 52 cards, including red face cards and red aces.
+    Full,
+}
+
+impl DeckVariant {
+    /// Number of cards in a deck of this variant.
+    pub fn size(&self) -> usize {
+        match self {
+            DeckVariant::Official => 44,
+            DeckVariant::Full => 52,
+        }
+    }
+
+    /// Returns true if the card is part of a deck of this variant.
+    pub fn includes(&self, card: &Card) -> bool {
+        match self {
+            DeckVariant::Official => !matches!(
+                card,
+                Card {
+                    suite: Suite::Heart | Suite::Diamond,
+                    rank: Rank::Jack | Rank::Queen | Rank::King | Rank::Ace,
+                }
+            ),
+            DeckVariant::Full => true,
+        }
+    }
+}
+
+impl Display for DeckVariant {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeckVariant::Official => write!(f, "Official ({} cards)", self.size()),
+            DeckVariant::Full => write!(f, "Full ({} cards)", self.size()),
+        }
+    }
+}
+
+/// The dungeon: an ordered draw pile, where cards are drawn from the top and avoided rooms go to the bottom.
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Deck {
-    cards: Vec<Card>,
+    variant: DeckVariant,
+    cards: VecDeque<Card>, // the front is the top of the deck, and the back is the bottom
+    num_known_cards_at_bottom: usize, // cards put at the bottom are known to the player, until they are drawn again
+    #[serde(default)]
+    num_fixed_cards: Option<usize>, // number of cards of a deck built from a list of cards, rather than the whole variant
 }
 impl Deck {
-    fn new() -> Self {
+    /// Builds a deck shuffled with an RNG seeded from `seed`.
+    /// ChaCha8 gives the same numbers for a seed in every version of `rand`, unlike `StdRng`,
+    /// so that a seed deals the same dungeon in every version of the game.
+    pub fn new(variant: DeckVariant, seed: Seed) -> Self {
+        Self::from_rng(variant, &mut ChaCha8Rng::seed_from_u64(seed))
+    }
+
+    /// Builds a deck shuffled once, up front, with the given RNG.
+    pub fn from_rng<R: RngCore>(variant: DeckVariant, rng: &mut R) -> Self {
         let mut cards = Vec::new();
 
         for suite in Suite::iter() {
             for rank in Rank::iter() {
                 let card = Card { suite, rank };
-                cards.push(card);
+                if variant.includes(&card) {
+                    cards.push(card);
+                }
             }
         }
 
-        assert_eq!(cards.len(), MAX_DECK_SIZE);
+        assert_eq!(cards.len(), variant.size());
 
-        Self { cards }
-    }
+        shuffle(&mut cards, rng);
 
-    pub fn draw_card(&mut self) -> Option<Card> {
-        if self.cards.is_empty() {
-            return None;
+        Self {
+            variant,
+            cards: cards.into(),
+            num_known_cards_at_bottom: 0,
+            num_fixed_cards: None,
         }
+    }
 
-        let mut rng = rng();
+    /// Builds a deck that holds the given cards in the given order, from the top - e.g. for the tutorial,
+    /// which needs to deal rooms that teach the rules.
+    pub fn from_cards(variant: DeckVariant, cards: &[Card]) -> Self {
+        assert!(cards.iter().all(|card| variant.includes(card)));
 
-        // pick a card at random
-        let num_cards_left = self.cards.len();
-        let chosen_card_idx = rng.random_range(0..num_cards_left);
-        let chosen_card = self.cards.remove(chosen_card_idx);
+        Self {
+            variant,
+            cards: cards.iter().copied().collect(),
+            num_known_cards_at_bottom: 0,
+            num_fixed_cards: Some(cards.len()),
+        }
+    }
 
-        Some(chosen_card)
+    /// Generates a new random seed.
+    pub fn random_seed() -> Seed {
+        rand::rng().random()
     }
 
-    pub fn insert_card(&mut self, card: Card) {
+    /// Draws the card at the top of the deck.
+    pub fn draw_card(&mut self) -> Option<Card> {
+        let card = self.cards.pop_front();
+        self.num_known_cards_at_bottom = self.num_known_cards_at_bottom.min(self.cards.len());
+        card
+    }
+
+    /// Puts a card at the bottom of the deck, so it is drawn after every card already in the deck.
+    pub fn put_card_at_bottom(&mut self, card: Card) {
         // TODO: check if card already exists in deck before inserting it
         //         we should not have two copies of a card in a Deck
-        self.cards.push(card);
+        self.cards.push_back(card);
+        self.num_known_cards_at_bottom += 1;
+    }
+
+    /// Shuffles the cards that the player has not seen, leaving the known cards at the bottom where they are -
+    /// so that a bot can look ahead on a copy of the deck without knowing its order.
+    /// The cards are sorted before they are shuffled, so the result does not depend on their order either.
+    pub fn shuffle_unseen_cards<R: RngCore>(&mut self, rng: &mut R) {
+        let num_unseen_cards = self.cards.len() - self.num_known_cards_at_bottom;
+        let unseen_cards = &mut self.cards.make_contiguous()[..num_unseen_cards];
+        unseen_cards.sort_by_key(|card| (card.suite as u8, card.rank as u8));
+        shuffle(unseen_cards, rng);
+    }
+
+    /// Iterates over the cards that the player has put at the bottom of the deck, from top to bottom.
+    pub fn known_cards_at_bottom(&'_ self) -> impl Iterator<Item = &'_ Card> {
+        self.cards
+            .iter()
+            .skip(self.cards.len() - self.num_known_cards_at_bottom)
     }
 
     pub fn len(&self) -> usize {
         self.cards.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    pub fn variant(&self) -> DeckVariant {
+        self.variant
+    }
+
+    /// Number of cards the deck started with.
+    pub fn size(&self) -> usize {
+        self.num_fixed_cards.unwrap_or(self.variant.size())
+    }
+
     pub fn iter(&'_ self) -> Iter<'_, Card> {
         self.cards.iter()
     }
 }
 
+/// Shuffles the cards with the Fisher-Yates shuffle. `rand` may change how its own shuffle uses the RNG
+/// between versions, which would change the deck dealt for a seed - this one only relies on the RNG.
+fn shuffle<R: RngCore>(cards: &mut [Card], rng: &mut R) {
+    for idx in (1..cards.len()).rev() {
+        cards.swap(idx, random_below(rng, idx as u64 + 1) as usize);
+    }
+}
+
+/// Draws a number below `bound`, with every number as likely - numbers at the top of the range of the
+/// RNG are drawn again, as they would make the lowest numbers more likely.
+fn random_below<R: RngCore>(rng: &mut R, bound: u64) -> u64 {
+    let limit = u64::MAX - u64::MAX % bound;
+    loop {
+        let number = rng.next_u64();
+        if number < limit {
+            return number % bound;
+        }
+    }
+}
+
 impl Default for Deck {
     fn default() -> Self {
-        Deck::new()
+        Deck::new(DeckVariant::default(), Deck::random_seed())
     }
 }
 
-#[derive(Hash, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize, Debug)]
 pub struct Card {
     pub suite: Suite,
     pub rank: Rank,
@@ -75,7 +207,7 @@ impl Display for Card {
     }
 }
 
-#[derive(Clone, Copy, Hash, PartialEq, Eq, EnumIter, Debug)]
+#[derive(Clone, Copy, Hash, PartialEq, Eq, EnumIter, Serialize, Deserialize, Debug)]
 pub enum Suite {
     Spade,
     Club,
@@ -83,7 +215,7 @@ pub enum Suite {
     Heart,
 }
 
-#[derive(Clone, Copy, Hash, PartialEq, Eq, EnumIter, Debug)]
+#[derive(Clone, Copy, Hash, PartialEq, Eq, EnumIter, Serialize, Deserialize, Debug)]
 pub enum Rank {
     Two,
     Three,
@@ -124,18 +256,92 @@ impl Value for Rank {
     }
 }
 
+#[cfg(test)]
 mod test {
-    use crate::cards::deck::Deck;
+    use crate::cards::deck::{Card, Deck, DeckVariant, Rank, Suite};
     use std::collections::HashSet;
 
     #[test]
     fn cards_should_only_be_drawn_once() {
         let mut cards_drawn = HashSet::new();
 
-        let mut deck = Deck::new();
+        let mut deck = Deck::default();
         while let Some(card) = deck.draw_card() {
             assert!(!cards_drawn.contains(&card));
             cards_drawn.insert(card);
         }
     }
+
+    #[test]
+    fn same_seed_should_give_same_order() {
+        let mut deck = Deck::new(DeckVariant::Official, 42);
+        let mut same_deck = Deck::new(DeckVariant::Official, 42);
+
+        while let Some(card) = deck.draw_card() {
+            assert_eq!(Some(card), same_deck.draw_card());
+        }
+        assert_eq!(same_deck.draw_card(), None);
+    }
+
+    #[test]
+    fn seed_should_always_give_the_same_deck() {
+        // the dungeon of a seed must never change, or old seeds could not be played again
+        let mut deck = Deck::new(DeckVariant::Official, 42);
+        let mut cards = Vec::new();
+        while let Some(card) = deck.draw_card() {
+            cards.push(card.to_string());
+        }
+        assert_eq!(
+            cards,
+            [
+                "<Spade, Nine>", "<Club, Ten>", "<Diamond, Nine>", "<Club, Nine>",
+                "<Club, Seven>", "<Heart, Four>", "<Spade, Five>", "<Heart, Eight>",
+                "<Spade, Eight>", "<Spade, Four>", "<Diamond, Two>", "<Club, Jack>",
+                "<Heart, Six>", "<Club, Five>", "<Club, Six>", "<Spade, Jack>",
+                "<Club, Queen>", "<Heart, Five>", "<Diamond, Three>", "<Spade, Six>",
+                "<Diamond, Seven>", "<Club, Eight>", "<Club, Three>", "<Club, Four>",
+                "<Spade, Three>", "<Spade, Seven>", "<Spade, Two>", "<Heart, Ten>",
+                "<Diamond, Six>", "<Diamond, Eight>", "<Spade, King>", "<Club, Two>",
+                "<Spade, Queen>", "<Heart, Three>", "<Diamond, Five>", "<Diamond, Four>",
+                "<Spade, Ace>", "<Spade, Ten>", "<Heart, Two>", "<Club, King>",
+                "<Heart, Nine>", "<Diamond, Ten>", "<Heart, Seven>", "<Club, Ace>",
+            ]
+        );
+    }
+
+    #[test]
+    fn cards_put_at_bottom_should_be_drawn_last() {
+        let mut deck = Deck::new(DeckVariant::Official, 42);
+        let avoided_room: Vec<Card> = (0..4).filter_map(|_| deck.draw_card()).collect();
+        for card in avoided_room.iter() {
+            deck.put_card_at_bottom(*card);
+        }
+        assert!(deck.known_cards_at_bottom().eq(avoided_room.iter()));
+
+        let mut cards_drawn = Vec::new();
+        while let Some(card) = deck.draw_card() {
+            cards_drawn.push(card);
+        }
+        assert_eq!(cards_drawn[cards_drawn.len() - 4..], avoided_room[..]);
+    }
+
+    #[test]
+    fn official_deck_should_not_have_red_face_cards_or_red_aces() {
+        let deck = Deck::new(DeckVariant::Official, 42);
+        assert_eq!(deck.len(), 44);
+
+        let red_face_cards_and_aces = deck.iter().filter(|card| {
+            matches!(card.suite, Suite::Heart | Suite::Diamond)
+                && matches!(card.rank, Rank::Jack | Rank::Queen | Rank::King | Rank::Ace)
+        });
+        assert_eq!(red_face_cards_and_aces.count(), 0);
+
+        let full_deck = Deck::new(DeckVariant::Full, 42);
+        assert_eq!(full_deck.len(), 52);
+        assert!(full_deck.iter().any(|card| *card
+            == Card {
+                suite: Suite::Heart,
+                rank: Rank::Ace
+            }));
+    }
 }
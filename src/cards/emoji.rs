@@ -0,0 +1,16 @@
+use crate::cards::deck::{Card, Suite};
+
+pub trait Emoji {
+    fn get_emoji(&self) -> String;
+}
+
+// implemented for cards, and for the classified cards (monsters, weapons and potions)
+impl<T: Copy + Into<Card>> Emoji for T {
+    fn get_emoji(&self) -> String {
+        match (*self).into().suite {
+            Suite::Spade | Suite::Club => "👺".to_string(),
+            Suite::Diamond => "⚔️".to_string(),
+            Suite::Heart => "❤️".to_string(),
+        }
+    }
+}
@@ -1,12 +1,14 @@
 use crate::cards::deck::Card;
+use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
-use std::slice::{Iter,IterMut};
+use std::slice::Iter;
 
 /// Represents a hand of cards that are drawn from the deck
-#[derive(Debug)]
+#[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct Hand {
     cards: Vec<Option<Card>>,
     num_cards: usize,
+    carried_over_slot: Option<usize>, // index of the card left over from the previous room, if any
 }
 
 impl Display for Hand {
@@ -14,9 +16,9 @@ impl Display for Hand {
         for (idx, card) in self.cards.iter().enumerate() {
             let card_num = idx + 1;
             match card {
-                Some(card) => write!(f, "[{:?}]: {card}\n", card_num)
+                Some(card) => writeln!(f, "[{:?}]: {card}", card_num)
                     .expect("Cannot print cards in hand to display."),
-                None => write!(f, "[{:?}]: CARD USED\n", card_num)
+                None => writeln!(f, "[{:?}]: CARD USED", card_num)
                     .expect("Cannot print cards in hand to display."),
             }
         }
@@ -29,9 +31,25 @@ impl Hand {
         Self {
             cards: Vec::new(),
             num_cards: 0,
+            carried_over_slot: None,
         }
     }
 
+    /// Marks the card left in the hand (if there is exactly one) as carried over to the next room.
+    /// The carried-over card keeps its slot, and new cards are dealt into the empty slots around it.
+    pub fn carry_over_last_card(&mut self) {
+        self.carried_over_slot = if self.num_cards == 1 {
+            self.cards.iter().position(|slot| slot.is_some())
+        } else {
+            None
+        };
+    }
+
+    /// Returns the card number (starting from 1) of the card carried over from the previous room, if any.
+    pub fn carried_over_card_num(&self) -> Option<usize> {
+        self.carried_over_slot.map(|idx| idx + 1)
+    }
+
     pub fn add_card(&mut self, card: Card) {
         if let Some(empty_slot) = self.cards.iter_mut().find(|slot| slot.is_none()) {
             *empty_slot = Some(card);
@@ -42,12 +60,15 @@ impl Hand {
     }
 
     pub fn remove_card(&mut self, card_num: usize) -> Option<Card> {
-        let idx = card_num - 1;
+        let idx = card_num.checked_sub(1)?;
         match self.cards.get_mut(idx) {
             Some(slot) => {
                 if slot.is_some() {
                     self.num_cards -= 1;
                 }
+                if self.carried_over_slot == Some(idx) {
+                    self.carried_over_slot = None;
+                }
                 slot.take()
             }
 
@@ -62,4 +83,44 @@ impl Hand {
     pub fn iter(&'_ self) -> Iter<'_, Option<Card>>  {
         self.cards.iter()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cards::deck::{Card, Rank, Suite};
+    use crate::cards::hand::Hand;
+
+    #[test]
+    fn carried_over_card_should_keep_its_slot() {
+        let mut hand = Hand::new();
+        for rank in [Rank::Two, Rank::Three, Rank::Four, Rank::Five] {
+            hand.add_card(Card {
+                suite: Suite::Spade,
+                rank,
+            });
+        }
+        for card_num in [1, 2, 4] {
+            hand.remove_card(card_num);
+        }
+
+        hand.carry_over_last_card();
+        for rank in [Rank::Six, Rank::Seven, Rank::Eight] {
+            hand.add_card(Card {
+                suite: Suite::Club,
+                rank,
+            });
+        }
+
+        assert_eq!(hand.carried_over_card_num(), Some(3));
+        assert_eq!(
+            hand.iter().nth(2),
+            Some(&Some(Card {
+                suite: Suite::Spade,
+                rank: Rank::Four
+            }))
+        );
+
+        hand.remove_card(3);
+        assert_eq!(hand.carried_over_card_num(), None);
+    }
+}
@@ -1,2 +1,4 @@
+pub mod classified;
 pub mod deck;
+pub mod emoji;
 pub mod hand;
\ No newline at end of file
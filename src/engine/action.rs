@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// A move that the player can make.
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub enum Action {
+    /// Enter the room that was dealt.
+    EnterRoom,
+    /// Avoid the room that was dealt - its cards go to the bottom of the deck.
+    AvoidRoom,
+    /// Pick the card with the given card number (starting from 1) in the room.
+    PickCard(usize),
+    /// Fight the creature with the equipped weapon.
+    FightWithWeapon,
+    /// Fight the creature bare-knuckle.
+    FightBareKnuckle,
+}
+
+impl Display for Action {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Action::EnterRoom => write!(f, "enter room"),
+            Action::AvoidRoom => write!(f, "avoid room"),
+            Action::PickCard(card_num) => write!(f, "pick card {card_num}"),
+            Action::FightWithWeapon => write!(f, "fight with weapon"),
+            Action::FightBareKnuckle => write!(f, "fight bare-knuckle"),
+        }
+    }
+}
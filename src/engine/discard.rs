@@ -0,0 +1,55 @@
+use crate::cards::deck::Card;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// How a card that was picked from a room was resolved.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub enum Resolution {
+    /// A potion that healed the player.
+    Healed { life_gained: u8 },
+    /// A potion used after another potion in the same room, which had no effect.
+    Wasted,
+    /// A weapon that was equipped.
+    Equipped,
+    /// A creature that was fought with the equipped weapon.
+    Blocked { damage_taken: u8 },
+    /// A creature that was fought bare-knuckle.
+    FoughtBareKnuckle { damage_taken: u8 },
+}
+
+impl Display for Resolution {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Resolution::Healed { life_gained } => write!(f, "healed {life_gained} life"),
+            Resolution::Wasted => write!(f, "wasted"),
+            Resolution::Equipped => write!(f, "equipped"),
+            Resolution::Blocked { damage_taken } => {
+                write!(f, "blocked, took {damage_taken} damage")
+            }
+            Resolution::FoughtBareKnuckle { damage_taken } => {
+                write!(f, "fought bare-knuckle, took {damage_taken} damage")
+            }
+        }
+    }
+}
+
+/// A card in the discard pile, with how it was resolved.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct Discard {
+    pub card: Card,
+    pub resolution: Resolution,
+}
+
+/// Where a card is, as far as the player can tell - as with a physical deck, the cards that
+/// are still in the dungeon can be counted from the ones that were already seen.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CardStatus {
+    /// The card was used, and is in the discard pile.
+    Used,
+    /// The card is in the current room, or is the creature being fought.
+    InRoom,
+    /// The card is still in the dungeon.
+    Remaining,
+    /// The card is not part of the dungeon, e.g. because the deck variant leaves it out.
+    NotInDungeon,
+}
@@ -0,0 +1,645 @@
+use crate::cards::classified::{ClassifiedCard, Monster, Potion, Weapon};
+use crate::cards::deck::{Card, Deck, DeckVariant, Seed, Value};
+use crate::cards::hand::Hand;
+use crate::engine::action::Action;
+use crate::engine::discard::{CardStatus, Discard, Resolution};
+use crate::engine::event::Event;
+use crate::engine::replay::Replay;
+use crate::engine::rule_error::RuleError;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+// CONSTANTS
+pub const MAX_LIFE: u8 = 20;
+pub const HAND_SIZE: usize = 4;
+pub const MAX_POTIONS_PER_ROOM: u8 = 1;
+
+/// The point of the game that the player is at, which decides the actions that can be taken.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub enum Phase {
+    /// A room was dealt - the player chooses to enter or avoid it.
+    BeforeRoom,
+    /// The player picks the next card in the room.
+    ChooseCard,
+    /// The player chooses to fight a creature with the equipped weapon, or bare-knuckle.
+    ChooseWeaponOrBareKnuckle,
+
+    Won,
+    Lost,
+}
+
+/// Statistics of a run, kept up to date as the game is played.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct RunStats {
+    pub rooms_cleared: u32,
+    pub turns: u32, // number of cards picked
+    pub damage_taken: u32,
+    pub potions_used: u32,
+}
+
+/// What is left in the dungeon (not counting the current room), for judging the risk of the rooms ahead.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DungeonStats {
+    pub monsters_left: usize,
+    pub total_monster_strength: i32, // the player loses this many points if they die
+    pub strongest_monster: Option<Monster>,
+    pub potions_left: usize,
+    pub total_healing: i32,
+    pub weapons_left: Vec<Weapon>, // strongest first
+}
+
+impl DungeonStats {
+    pub fn of(deck: &Deck) -> DungeonStats {
+        let monsters: Vec<Monster> = deck
+            .iter()
+            .filter_map(|card| Monster::try_from(*card).ok())
+            .collect();
+        let potions: Vec<Potion> = deck
+            .iter()
+            .filter_map(|card| Potion::try_from(*card).ok())
+            .collect();
+        let mut weapons_left: Vec<Weapon> = deck
+            .iter()
+            .filter_map(|card| Weapon::try_from(*card).ok())
+            .collect();
+        weapons_left.sort_by_key(|weapon| std::cmp::Reverse(weapon.get_value()));
+
+        DungeonStats {
+            monsters_left: monsters.len(),
+            total_monster_strength: total_monster_strength(deck),
+            strongest_monster: monsters.iter().max_by_key(|monster| monster.get_value()).copied(),
+            potions_left: potions.len(),
+            total_healing: potions.iter().map(|potion| potion.get_value() as i32).sum(),
+            weapons_left,
+        }
+    }
+}
+
+/// Total strength of the monsters left in the deck - shared with the score, which is computed often by the bots,
+/// so this does not collect the monsters first.
+fn total_monster_strength(deck: &Deck) -> i32 {
+    deck.iter()
+        .filter_map(|card| Monster::try_from(*card).ok())
+        .map(|monster| monster.get_value() as i32)
+        .sum()
+}
+
+/// Holds the state of a game, and enforces its rules.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Engine {
+    seed: Seed,
+    deck: Deck,
+    hand: Hand,
+    phase: Phase,
+
+    life: u8,
+    has_avoided_room: bool,
+    potions_used_in_room: u8, // only the first potion used in a room heals
+
+    equipped_weapon: Option<Weapon>,
+    blocked_creatures: Vec<Monster>, // a stack
+    in_combat_with_creature: Option<Monster>, // holds the creature that the player is currently fighting, while choosing to use the weapon or not
+
+    #[serde(default)]
+    stats: RunStats,
+    #[serde(default)]
+    actions: Vec<Action>, // every action applied so far, so that the game can be replayed
+    #[serde(default)]
+    discard_pile: Vec<Discard>, // cards that were used, in the order they were used
+}
+
+impl Engine {
+    /// Starts a new game with a deck shuffled from `seed`, and deals the first room.
+    pub fn new(variant: DeckVariant, seed: Seed) -> Self {
+        Self::with_deck(seed, Deck::new(variant, seed))
+    }
+
+    /// Starts a new game with the given deck, and deals the first room.
+    /// `seed` is only recorded, so the game cannot be replayed unless the deck was shuffled from it.
+    pub fn with_deck(seed: Seed, deck: Deck) -> Self {
+        let mut engine = Engine {
+            seed,
+            deck,
+            hand: Hand::new(),
+            phase: Phase::BeforeRoom,
+            life: MAX_LIFE,
+            has_avoided_room: false,
+            potions_used_in_room: 0,
+            equipped_weapon: None,
+            blocked_creatures: Vec::new(),
+            in_combat_with_creature: None,
+            stats: RunStats::default(),
+            actions: Vec::new(),
+            discard_pile: Vec::new(),
+        };
+        engine.draw_cards();
+
+        engine
+    }
+
+    pub fn seed(&self) -> Seed {
+        self.seed
+    }
+
+    pub fn deck(&self) -> &Deck {
+        &self.deck
+    }
+
+    pub fn hand(&self) -> &Hand {
+        &self.hand
+    }
+
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    pub fn life(&self) -> u8 {
+        self.life
+    }
+
+    pub fn potions_used_in_room(&self) -> u8 {
+        self.potions_used_in_room
+    }
+
+    pub fn equipped_weapon(&self) -> Option<&Weapon> {
+        self.equipped_weapon.as_ref()
+    }
+
+    pub fn blocked_creatures(&self) -> &[Monster] {
+        &self.blocked_creatures
+    }
+
+    pub fn in_combat_with_creature(&self) -> Option<&Monster> {
+        self.in_combat_with_creature.as_ref()
+    }
+
+    pub fn stats(&self) -> RunStats {
+        self.stats
+    }
+
+    /// Shuffles the cards of the dungeon that the player has not seen. Bots that look ahead do so on a
+    /// copy of the game with its unseen cards shuffled, so that they only know what the player knows.
+    pub(crate) fn shuffle_unseen_cards<R: RngCore>(&mut self, rng: &mut R) {
+        self.deck.shuffle_unseen_cards(rng);
+    }
+
+    pub fn dungeon_stats(&self) -> DungeonStats {
+        DungeonStats::of(&self.deck)
+    }
+
+    pub fn discard_pile(&self) -> &[Discard] {
+        &self.discard_pile
+    }
+
+    /// Returns where the card is.
+    pub fn card_status(&self, card: &Card) -> CardStatus {
+        if self.discard_pile.iter().any(|discard| discard.card == *card) {
+            CardStatus::Used
+        } else if self.hand.iter().flatten().any(|other| other == card)
+            || self
+                .in_combat_with_creature
+                .is_some_and(|creature| Card::from(creature) == *card)
+        {
+            CardStatus::InRoom
+        } else if self.deck.iter().any(|other| other == card) {
+            CardStatus::Remaining
+        } else {
+            // not part of the deck variant, or of a deck built from a list of cards
+            CardStatus::NotInDungeon
+        }
+    }
+
+    pub fn is_over(&self) -> bool {
+        matches!(self.phase, Phase::Won | Phase::Lost)
+    }
+
+    /// Returns the actions that can be applied at this point of the game.
+    pub fn legal_actions(&self) -> Vec<Action> {
+        match self.phase {
+            Phase::BeforeRoom => {
+                if self.has_avoided_room {
+                    vec![Action::EnterRoom]
+                } else {
+                    vec![Action::EnterRoom, Action::AvoidRoom]
+                }
+            }
+            Phase::ChooseCard => self
+                .hand
+                .iter()
+                .enumerate()
+                .filter(|(_, slot)| slot.is_some())
+                .map(|(idx, _)| Action::PickCard(idx + 1))
+                .collect(),
+            Phase::ChooseWeaponOrBareKnuckle => {
+                vec![Action::FightWithWeapon, Action::FightBareKnuckle]
+            }
+            Phase::Won | Phase::Lost => Vec::new(),
+        }
+    }
+
+    /// Applies the action, and returns the events that happened as a result.
+    /// The state of the game is left unchanged if the action is not allowed.
+    pub fn apply(&mut self, action: Action) -> Result<Vec<Event>, RuleError> {
+        let events = match action {
+            Action::EnterRoom => self.enter_room(),
+            Action::AvoidRoom => self.avoid_room(),
+            Action::PickCard(card_num) => self.pick_card(card_num),
+            Action::FightWithWeapon => self.fight_creature_with_weapon(),
+            Action::FightBareKnuckle => self.fight_creature_bare_knuckle(),
+        }?;
+        self.actions.push(action);
+
+        Ok(events)
+    }
+
+    /// Returns a recording of the actions applied through `apply` so far.
+    pub fn replay(&self) -> Replay {
+        Replay {
+            variant: self.deck.variant(),
+            seed: self.seed,
+            actions: self.actions.clone(),
+        }
+    }
+
+    fn enter_room(&mut self) -> Result<Vec<Event>, RuleError> {
+        self.check_before_room()?;
+
+        self.phase = Phase::ChooseCard;
+        Ok(vec![Event::RoomEntered])
+    }
+
+    /// Puts the cards of the room at the bottom of the deck, and deals a new room that has to be entered.
+    fn avoid_room(&mut self) -> Result<Vec<Event>, RuleError> {
+        if !self.is_over() && self.has_avoided_room {
+            return Err(RuleError::CannotAvoidTwice);
+        }
+        self.check_before_room()?;
+
+        self.put_back_cards();
+        self.has_avoided_room = true;
+        self.draw_cards();
+
+        // a room cannot be avoided twice in a row, so the player has to enter the new room
+        self.phase = Phase::ChooseCard;
+        Ok(vec![Event::RoomAvoided, Event::RoomDealt])
+    }
+
+    /// Uses the card with the given card number (starting from 1) in the room.
+    fn pick_card(&mut self, card_num: usize) -> Result<Vec<Event>, RuleError> {
+        match self.phase {
+            Phase::Won | Phase::Lost => return Err(RuleError::GameAlreadyOver),
+            Phase::BeforeRoom => return Err(RuleError::RoomNotEntered),
+            Phase::ChooseWeaponOrBareKnuckle => return Err(self.in_combat_error()),
+            Phase::ChooseCard => {}
+        }
+
+        if card_num == 0 || card_num > self.hand.iter().len() {
+            return Err(RuleError::NoSuchCard(card_num));
+        }
+        let card = self
+            .hand
+            .remove_card(card_num)
+            .ok_or(RuleError::CardAlreadyUsed(card_num))?;
+        self.stats.turns += 1;
+
+        let mut events = Vec::new();
+        match ClassifiedCard::from(card) {
+            ClassifiedCard::Potion(potion) => events.push(self.add_to_life(potion)),
+            ClassifiedCard::Weapon(weapon) => events.push(self.equip_weapon(weapon)),
+            ClassifiedCard::Monster(creature) => {
+                self.in_combat_with_creature = Some(creature);
+
+                if self.check_weapon_can_be_used_on(&creature).is_ok() {
+                    // give the player a choice whether to use the weapon, or to bare-knuckle
+                    self.phase = Phase::ChooseWeaponOrBareKnuckle;
+                    events.push(Event::CombatStarted { creature });
+                    return Ok(events);
+                }
+
+                // no suitable weapon equipped, only choice is to bare-knuckle
+                events.append(&mut self.fight_creature_bare_knuckle()?);
+                return Ok(events);
+            }
+        }
+
+        self.end_turn(&mut events);
+        Ok(events)
+    }
+
+    fn fight_creature_with_weapon(&mut self) -> Result<Vec<Event>, RuleError> {
+        let creature = self.check_in_combat()?;
+        self.check_weapon_can_be_used_on(&creature)?;
+        let weapon_strength = self
+            .equipped_weapon
+            .as_ref()
+            .ok_or(RuleError::NoWeaponEquipped)?
+            .get_value();
+
+        self.in_combat_with_creature = None;
+        let damage_taken = creature.get_value().saturating_sub(weapon_strength) as u8;
+
+        // update life points
+        self.life = self.life.saturating_sub(damage_taken);
+        self.stats.damage_taken += damage_taken as u32;
+
+        // track list of creatures that were blocked
+        self.blocked_creatures.push(creature);
+        self.discard(creature, Resolution::Blocked { damage_taken });
+
+        let mut events = vec![Event::CreatureBlocked {
+            creature,
+            damage_taken,
+        }];
+        self.end_turn(&mut events);
+        Ok(events)
+    }
+
+    fn fight_creature_bare_knuckle(&mut self) -> Result<Vec<Event>, RuleError> {
+        let creature = self.check_in_combat()?;
+
+        self.in_combat_with_creature = None;
+        let damage_taken = creature.get_value() as u8;
+
+        // subtract life points
+        self.life = self.life.saturating_sub(damage_taken);
+        self.stats.damage_taken += damage_taken as u32;
+        self.discard(creature, Resolution::FoughtBareKnuckle { damage_taken });
+
+        let mut events = vec![Event::CreatureFoughtBareKnuckle {
+            creature,
+            damage_taken,
+        }];
+        self.end_turn(&mut events);
+        Ok(events)
+    }
+
+    fn check_before_room(&self) -> Result<(), RuleError> {
+        match self.phase {
+            Phase::Won | Phase::Lost => Err(RuleError::GameAlreadyOver),
+            Phase::ChooseCard => Err(RuleError::RoomAlreadyEntered),
+            Phase::ChooseWeaponOrBareKnuckle => Err(self.in_combat_error()),
+            Phase::BeforeRoom => Ok(()),
+        }
+    }
+
+    /// Returns the creature that the player is fighting.
+    fn check_in_combat(&self) -> Result<Monster, RuleError> {
+        if self.is_over() {
+            return Err(RuleError::GameAlreadyOver);
+        }
+        self.in_combat_with_creature
+            .ok_or(RuleError::NotInCombat)
+    }
+
+    fn in_combat_error(&self) -> RuleError {
+        match self.in_combat_with_creature {
+            Some(creature) => RuleError::InCombat { creature },
+            None => RuleError::NotInCombat,
+        }
+    }
+
+    /// A weapon can only be used on a creature weaker than the last creature it blocked.
+    fn check_weapon_can_be_used_on(&self, creature: &Monster) -> Result<(), RuleError> {
+        if self.equipped_weapon.is_none() {
+            return Err(RuleError::NoWeaponEquipped);
+        }
+
+        match self.blocked_creatures.last() {
+            Some(last_blocked_creature)
+                if creature.get_value() >= last_blocked_creature.get_value() =>
+            {
+                Err(RuleError::WeaponCannotBeUsed {
+                    creature: *creature,
+                    last_blocked_creature: *last_blocked_creature,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Draws cards from the deck up to hand size.
+    /// Returns false if the deck was already empty, and true otherwise - depending on the deck variant,
+    /// the last room may hold fewer cards than the hand size.
+    fn draw_cards(&mut self) -> bool {
+        if self.deck.is_empty() {
+            return false;
+        }
+
+        self.hand.carry_over_last_card();
+
+        let number_of_cards_to_draw = HAND_SIZE - self.hand.num_cards_remaining();
+
+        for _ in 0..number_of_cards_to_draw {
+            if let Some(card) = self.deck.draw_card() {
+                self.hand.add_card(card);
+            }
+        }
+
+        true
+    }
+
+    /// Puts the cards of the avoided room at the bottom of the deck, in the order they were in the room.
+    fn put_back_cards(&mut self) {
+        for idx in 0..HAND_SIZE {
+            let card_num = idx + 1;
+            if let Some(card) = self.hand.remove_card(card_num) {
+                self.deck.put_card_at_bottom(card);
+            }
+        }
+    }
+
+    /// Ends the game if the player has died, deals the next room once a single card is left in the room,
+    /// and ends the game once the whole dungeon has been cleared.
+    fn end_turn(&mut self, events: &mut Vec<Event>) {
+        if self.life == 0 {
+            self.phase = Phase::Lost;
+            events.push(Event::GameLost {
+                score: self.calculate_score(),
+            });
+        } else if self.hand.num_cards_remaining() <= 1 {
+            self.stats.rooms_cleared += 1;
+            self.has_avoided_room = false;
+            self.potions_used_in_room = 0;
+
+            if self.draw_cards() {
+                self.phase = Phase::BeforeRoom;
+                events.push(Event::RoomDealt);
+            } else {
+                self.phase = Phase::Won;
+                events.push(Event::GameWon {
+                    score: self.calculate_score(),
+                });
+            }
+        } else {
+            self.phase = Phase::ChooseCard;
+        }
+    }
+
+    /// Adds points from Heart cards to player's life points, up to a maximum of 20 points
+    /// - only the first potion used in a room heals, any other potion in the same room is discarded
+    fn add_to_life(&mut self, potion: Potion) -> Event {
+        self.potions_used_in_room += 1;
+        if self.potions_used_in_room > MAX_POTIONS_PER_ROOM {
+            self.discard(potion, Resolution::Wasted);
+            return Event::PotionWasted { potion };
+        }
+        self.stats.potions_used += 1;
+
+        let life_points_given_by_card = potion.get_value() as u8;
+        let life_points_addable: u8 = MAX_LIFE - self.life; // player's life cannot exceed MAX_LIFE points
+        let life_gained = std::cmp::min(life_points_given_by_card, life_points_addable);
+        self.life = self.life.saturating_add(life_gained);
+        self.discard(potion, Resolution::Healed { life_gained });
+
+        Event::Healed {
+            potion,
+            life_gained,
+        }
+    }
+
+    fn equip_weapon(&mut self, weapon: Weapon) -> Event {
+        self.equipped_weapon = Some(weapon);
+        self.blocked_creatures.clear(); // reset list of blocked creatures
+        self.discard(weapon, Resolution::Equipped);
+
+        Event::WeaponEquipped { weapon }
+    }
+
+    fn discard(&mut self, card: impl Into<Card>, resolution: Resolution) {
+        self.discard_pile.push(Discard {
+            card: card.into(),
+            resolution,
+        });
+    }
+
+    // used to calculate score at the end of the game
+    pub fn calculate_score(&self) -> i32 {
+        if self.life > 0 {
+            let score = if self.hand.num_cards_remaining() == 1 {
+                let bonus_score = self
+                    .hand
+                    .iter()
+                    .find_map(|slot| slot.and_then(|card| Potion::try_from(card).ok()))
+                    .map_or_else(|| 0, |potion| potion.get_value());
+
+                self.life + (bonus_score as u8)
+            } else {
+                self.life
+            };
+
+            score as i32
+        } else {
+            -total_monster_strength(&self.deck)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cards::deck::{Card, DeckVariant, Rank, Suite, Value};
+    use crate::engine::action::Action;
+    use crate::engine::discard::CardStatus;
+    use crate::engine::engine::{Engine, MAX_LIFE, Phase};
+    use crate::engine::rule_error::RuleError;
+    use strum::IntoEnumIterator;
+
+    /// Plays a game to the end, always picking the first legal action.
+    fn play_first_legal_actions(engine: &mut Engine) {
+        while let Some(action) = engine.legal_actions().first().copied() {
+            engine
+                .apply(action)
+                .expect("legal actions should be allowed");
+        }
+    }
+
+    #[test]
+    fn legal_actions_should_play_a_game_to_the_end() {
+        for seed in 0..20 {
+            let mut engine = Engine::new(DeckVariant::Official, seed);
+            play_first_legal_actions(&mut engine);
+
+            assert!(engine.is_over());
+            assert!(engine.life() <= MAX_LIFE);
+            assert_eq!(
+                engine.apply(Action::EnterRoom),
+                Err(RuleError::GameAlreadyOver)
+            );
+        }
+    }
+
+    #[test]
+    fn room_cannot_be_avoided_twice_in_a_row() {
+        let mut engine = Engine::new(DeckVariant::Official, 42);
+        engine.apply(Action::AvoidRoom).unwrap();
+
+        assert_eq!(engine.phase(), Phase::ChooseCard);
+        assert_eq!(
+            engine.apply(Action::AvoidRoom),
+            Err(RuleError::CannotAvoidTwice)
+        );
+    }
+
+    #[test]
+    fn every_card_should_be_in_the_room_the_dungeon_or_the_discard_pile() {
+        let mut engine = Engine::new(DeckVariant::Official, 42);
+        for action in [Action::AvoidRoom, Action::PickCard(2), Action::PickCard(4)] {
+            engine.apply(action).unwrap();
+        }
+        let count = |status| {
+            Suite::iter()
+                .flat_map(|suite| Rank::iter().map(move |rank| Card { suite, rank }))
+                .filter(|card| engine.card_status(card) == status)
+                .count()
+        };
+
+        assert_eq!(count(CardStatus::Used), engine.discard_pile().len());
+        assert_eq!(count(CardStatus::Remaining), engine.deck().len());
+        assert_eq!(
+            count(CardStatus::Used) + count(CardStatus::InRoom) + count(CardStatus::Remaining),
+            DeckVariant::Official.size()
+        );
+    }
+
+    #[test]
+    fn dungeon_stats_should_count_the_cards_left_in_the_deck() {
+        let stats = Engine::new(DeckVariant::Official, 42).dungeon_stats();
+
+        // the first room is dealt from the 26 monsters, 9 weapons and 9 potions of the official deck
+        assert_eq!(stats.monsters_left + stats.potions_left + stats.weapons_left.len(), 40);
+        assert!(stats.total_monster_strength <= 2 * (2..=14).sum::<i32>());
+        assert!(stats.total_healing <= (2..=10).sum::<i32>());
+        assert!(
+            stats
+                .weapons_left
+                .windows(2)
+                .all(|weapons| weapons[0].get_value() >= weapons[1].get_value())
+        );
+    }
+
+    #[test]
+    fn actions_out_of_sequence_should_return_errors() {
+        let mut engine = Engine::new(DeckVariant::Official, 42);
+        assert_eq!(
+            engine.apply(Action::PickCard(1)),
+            Err(RuleError::RoomNotEntered)
+        );
+        assert_eq!(
+            engine.apply(Action::FightWithWeapon),
+            Err(RuleError::NotInCombat)
+        );
+
+        engine.apply(Action::EnterRoom).unwrap();
+        assert_eq!(
+            engine.apply(Action::EnterRoom),
+            Err(RuleError::RoomAlreadyEntered)
+        );
+        assert_eq!(
+            engine.apply(Action::PickCard(0)),
+            Err(RuleError::NoSuchCard(0))
+        );
+        assert_eq!(
+            engine.apply(Action::PickCard(5)),
+            Err(RuleError::NoSuchCard(5))
+        );
+    }
+}
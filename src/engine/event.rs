@@ -0,0 +1,28 @@
+use crate::cards::classified::{Monster, Potion, Weapon};
+
+/// Something that happened as the result of an action.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Event {
+    /// A new room was dealt from the deck.
+    RoomDealt,
+    /// The player entered the room.
+    RoomEntered,
+    /// The player avoided the room, and its cards were put at the bottom of the deck.
+    RoomAvoided,
+    /// A potion was used, and healed the player.
+    Healed { potion: Potion, life_gained: u8 },
+    /// A potion was used after another potion in the same room, and had no effect.
+    PotionWasted { potion: Potion },
+    /// A weapon was equipped, replacing the previous one (if any).
+    WeaponEquipped { weapon: Weapon },
+    /// The player has to choose whether to fight the creature with the equipped weapon, or bare-knuckle.
+    CombatStarted { creature: Monster },
+    /// The creature was fought with the equipped weapon.
+    CreatureBlocked { creature: Monster, damage_taken: u8 },
+    /// The creature was fought bare-knuckle.
+    CreatureFoughtBareKnuckle { creature: Monster, damage_taken: u8 },
+    /// The whole dungeon was cleared.
+    GameWon { score: i32 },
+    /// The player ran out of life points.
+    GameLost { score: i32 },
+}
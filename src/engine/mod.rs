@@ -0,0 +1,12 @@
+//! The rules of Scoundrel, free of any UI concerns.
+//!
+//! Front-ends (the TUI, bots, tests) drive a game by asking the [`engine::Engine`] for its
+//! `legal_actions()`, and then calling `apply(action)`, which returns the events that happened.
+pub mod action;
+pub mod discard;
+#[allow(clippy::module_inception)]
+pub mod engine;
+pub mod event;
+pub mod replay;
+pub mod rule_error;
+pub mod rules;
@@ -0,0 +1,104 @@
+use crate::cards::deck::{DeckVariant, Seed};
+use crate::engine::action::Action;
+use crate::engine::engine::Engine;
+use crate::engine::rule_error::RuleError;
+use serde::{Deserialize, Serialize};
+
+/// A recording of a game - as the engine is deterministic, the game is played again by
+/// applying the actions, in order, to a new engine with the same deck variant and seed.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct Replay {
+    pub variant: DeckVariant,
+    pub seed: Seed,
+    pub actions: Vec<Action>,
+}
+
+impl Replay {
+    /// Plays the game again, and returns the state of the game before the first action,
+    /// and after each action (so there is one more state than there are actions).
+    pub fn play(&self) -> Result<Vec<Engine>, RuleError> {
+        let mut engine = Engine::new(self.variant, self.seed);
+        let mut states = vec![engine.clone()];
+
+        for action in &self.actions {
+            engine.apply(*action)?;
+            states.push(engine.clone());
+        }
+
+        Ok(states)
+    }
+
+    /// Describes each move of the game, e.g. "Room 2: Picked <Spade, Five> (life 20 -> 15)".
+    pub fn describe_moves(&self) -> Result<Vec<String>, RuleError> {
+        let states = self.play()?;
+
+        Ok(states
+            .windows(2)
+            .zip(&self.actions)
+            .map(|(states, action)| describe_move(&states[0], *action, &states[1]))
+            .collect())
+    }
+}
+
+/// Describes a move, from the state of the game before and after it.
+fn describe_move(before: &Engine, action: Action, after: &Engine) -> String {
+    let description = match action {
+        Action::EnterRoom => "Entered the room".to_string(),
+        Action::AvoidRoom => "Fled the room".to_string(),
+        Action::PickCard(card_num) => {
+            match card_num
+                .checked_sub(1)
+                .and_then(|idx| before.hand().iter().nth(idx).copied().flatten())
+            {
+                Some(card) => format!("Picked {card}"),
+                None => action.to_string(),
+            }
+        }
+        Action::FightWithWeapon | Action::FightBareKnuckle => {
+            match before.in_combat_with_creature() {
+                Some(creature) if action == Action::FightWithWeapon => {
+                    format!("Fought {creature} with the weapon")
+                }
+                Some(creature) => format!("Fought {creature} bare-knuckle"),
+                None => action.to_string(),
+            }
+        }
+    };
+    let room = before.stats().rooms_cleared + 1;
+
+    if after.life() == before.life() {
+        format!("Room {room}: {description}")
+    } else {
+        format!(
+            "Room {room}: {description} (life {} -> {})",
+            before.life(),
+            after.life()
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cards::deck::DeckVariant;
+    use crate::engine::action::Action;
+    use crate::engine::engine::Engine;
+
+    #[test]
+    fn replay_should_end_in_the_same_state_as_the_game() {
+        let mut engine = Engine::new(DeckVariant::Full, 7);
+        while let Some(action) = engine.legal_actions().last().copied() {
+            engine.apply(action).unwrap();
+        }
+        // actions that are not allowed are not recorded
+        assert!(engine.apply(Action::EnterRoom).is_err());
+
+        let replay = engine.replay();
+        let states = replay.play().unwrap();
+
+        assert_eq!(states.len(), replay.actions.len() + 1);
+        let last_state = states.last().unwrap();
+        assert_eq!(last_state.phase(), engine.phase());
+        assert_eq!(last_state.calculate_score(), engine.calculate_score());
+        assert_eq!(last_state.replay(), replay);
+    }
+}
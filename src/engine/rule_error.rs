@@ -0,0 +1,57 @@
+use crate::cards::classified::Monster;
+use std::fmt::{Display, Formatter};
+
+/// Returned when an action is not allowed by the rules - the state of the game is left unchanged.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum RuleError {
+    /// The game has already been won or lost.
+    GameAlreadyOver,
+    /// The room has to be entered (or avoided) before its cards can be picked.
+    RoomNotEntered,
+    /// The room has already been entered, so it can no longer be entered or avoided.
+    RoomAlreadyEntered,
+    /// A room cannot be avoided twice in a row.
+    CannotAvoidTwice,
+    /// There is no card with the given card number in the room.
+    NoSuchCard(usize),
+    /// The card with the given card number has already been used.
+    CardAlreadyUsed(usize),
+    /// The player has to choose how to fight the current creature first.
+    InCombat { creature: Monster },
+    /// The player is not fighting any creature.
+    NotInCombat,
+    /// The player has no weapon to fight with.
+    NoWeaponEquipped,
+    /// The weapon can only be used on creatures weaker than the last creature it blocked.
+    WeaponCannotBeUsed {
+        creature: Monster,
+        last_blocked_creature: Monster,
+    },
+}
+
+impl Display for RuleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RuleError::GameAlreadyOver => write!(f, "The game is already over!"),
+            RuleError::RoomNotEntered => write!(f, "You have to enter the room first!"),
+            RuleError::RoomAlreadyEntered => write!(f, "You have already entered the room!"),
+            RuleError::CannotAvoidTwice => write!(f, "Cannot avoid two rooms in a row!"),
+            RuleError::NoSuchCard(card_num) => write!(f, "There is no card {card_num} in the room!"),
+            RuleError::CardAlreadyUsed(_) => write!(f, "Card has already been used!"),
+            RuleError::InCombat { creature } => {
+                write!(f, "You have to choose how to fight {creature} first!")
+            }
+            RuleError::NotInCombat => write!(f, "You are not fighting any creature!"),
+            RuleError::NoWeaponEquipped => write!(f, "You have no weapon equipped!"),
+            RuleError::WeaponCannotBeUsed {
+                creature,
+                last_blocked_creature,
+            } => write!(
+                f,
+                "Your weapon cannot be used on {creature}, as it is not weaker than {last_blocked_creature}!"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RuleError {}
@@ -0,0 +1,188 @@
+//! The rules of the game written out for the player.
+//!
+//! The text is built from the deck variant and the constants that the engine enforces,
+//! so it cannot drift from the rules in play.
+use crate::cards::classified::CardKind;
+use crate::cards::deck::{Card, Deck, DeckVariant, Rank, Suite, Value};
+use crate::engine::engine::{DungeonStats, HAND_SIZE, MAX_LIFE, MAX_POTIONS_PER_ROOM};
+use strum::IntoEnumIterator;
+
+/// A part of the rules, e.g. how combat works.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RulesSection {
+    pub title: &'static str,
+    pub paragraphs: Vec<String>,
+}
+
+/// Writes out the rules of a game played with a deck of the variant.
+pub fn describe_rules(variant: DeckVariant) -> Vec<RulesSection> {
+    // a full dungeon, as it is before the first room is dealt
+    let dungeon = DungeonStats::of(&Deck::new(variant, 0));
+    let cards_played_per_room = HAND_SIZE - 1;
+
+    vec![
+        RulesSection {
+            title: "The dungeon",
+            paragraphs: vec![
+                format!(
+                    "You are a lone rogue in a dungeon of {} cards - the {:?} deck. {}",
+                    variant.size(),
+                    variant,
+                    describe_cards_left_out(variant)
+                ),
+                format!(
+                    "You start with {MAX_LIFE} life. You win by playing through every card of the dungeon, and you die if your life drops to 0."
+                ),
+            ],
+        },
+        RulesSection {
+            title: "Suits",
+            paragraphs: vec![
+                format!(
+                    "Spades and Clubs are monsters, as strong as their rank: {}. There are {} of them, of {} strength in total.",
+                    describe_ranks(variant, CardKind::Monster),
+                    dungeon.monsters_left,
+                    dungeon.total_monster_strength
+                ),
+                format!(
+                    "Diamonds are weapons, as strong as their rank: {}.",
+                    describe_ranks(variant, CardKind::Weapon)
+                ),
+                format!(
+                    "Hearts are potions, which heal as much life as their rank: {}. There are {} of them, which heal {} life in total.",
+                    describe_ranks(variant, CardKind::Potion),
+                    dungeon.potions_left,
+                    dungeon.total_healing
+                ),
+            ],
+        },
+        RulesSection {
+            title: "Rooms",
+            paragraphs: vec![format!(
+                "The dungeon is dealt {HAND_SIZE} cards at a time into a room. Once you enter a room, you play {cards_played_per_room} of its cards, in any order. The last card stays, and the next room is dealt around it."
+            )],
+        },
+        RulesSection {
+            title: "Fleeing",
+            paragraphs: vec![
+                format!(
+                    "Before you enter a room, you can flee from it: its {HAND_SIZE} cards go to the bottom of the dungeon, in the same order, and a new room is dealt."
+                ),
+                "You cannot flee twice in a row - the room dealt after fleeing has to be entered. Once a room has been cleared, you can flee again.".to_string(),
+            ],
+        },
+        RulesSection {
+            title: "Combat",
+            paragraphs: vec![
+                "Without a weapon, a monster is fought bare-knuckle: you lose as much life as it is strong.".to_string(),
+                "With a weapon, you choose between fighting bare-knuckle and blocking with the weapon, which only takes the damage left after the strength of the weapon - a 9 blocked with a 6 takes 3 damage.".to_string(),
+            ],
+        },
+        RulesSection {
+            title: "Weapons",
+            paragraphs: vec![
+                "Picking a weapon equips it, replacing the weapon you had.".to_string(),
+                "A weapon dulls: once it has blocked a monster, it can only block monsters weaker than the last monster it blocked. A weapon that blocked a 6 can block a 5, but not another 6. Fighting bare-knuckle leaves the weapon as it is, and equipping a new weapon starts afresh.".to_string(),
+            ],
+        },
+        RulesSection {
+            title: "Potions",
+            paragraphs: vec![format!(
+                "A potion heals you by its rank, up to {MAX_LIFE} life. {}",
+                describe_potion_limit()
+            )],
+        },
+        RulesSection {
+            title: "Scoring",
+            paragraphs: vec![
+                "If you clear the dungeon, your score is the life you have left - plus the rank of the last card of the room, if it is a potion.".to_string(),
+                "If you die, your score is minus the total strength of the monsters left in the dungeon, not counting the room you died in.".to_string(),
+            ],
+        },
+    ]
+}
+
+/// Lists the cards of a full deck that the variant leaves out.
+fn describe_cards_left_out(variant: DeckVariant) -> String {
+    // suits that leave out the same ranks are listed together, e.g. "the Aces of Diamonds and Hearts"
+    let mut left_out: Vec<(Vec<String>, Vec<String>)> = Vec::new();
+    for suite in Suite::iter() {
+        let ranks = Rank::iter()
+            .filter(|rank| !variant.includes(&Card { suite, rank: *rank }))
+            .map(|rank| format!("{rank:?}"))
+            .collect::<Vec<_>>();
+        if ranks.is_empty() {
+            continue;
+        }
+        match left_out.iter_mut().find(|(other, _)| *other == ranks) {
+            Some((_, suites)) => suites.push(format!("{suite:?}s")),
+            None => left_out.push((ranks, vec![format!("{suite:?}s")])),
+        }
+    }
+
+    if left_out.is_empty() {
+        return "Every card of the deck is used.".to_string();
+    }
+    let left_out = left_out
+        .iter()
+        .map(|(ranks, suites)| format!("{} of {}", join(ranks), join(suites)))
+        .collect::<Vec<_>>();
+    format!("The {} are left out.", join(&left_out))
+}
+
+/// Describes the ranks that cards of the kind have in the variant, e.g. "2 to 10".
+fn describe_ranks(variant: DeckVariant, kind: CardKind) -> String {
+    let values = Suite::iter()
+        .flat_map(|suite| Rank::iter().map(move |rank| Card { suite, rank }))
+        .filter(|card| CardKind::of(card) == kind && variant.includes(card))
+        .map(|card| card.rank.get_value());
+    let (min, max) = values.fold((usize::MAX, 0), |(min, max), value| {
+        (min.min(value), max.max(value))
+    });
+
+    // face cards and aces are worth more than 10
+    if max > Rank::Ten.get_value() {
+        format!("{min} to {max} (Jack 11, Queen 12, King 13, Ace 14)")
+    } else {
+        format!("{min} to {max}")
+    }
+}
+
+fn describe_potion_limit() -> String {
+    match MAX_POTIONS_PER_ROOM {
+        1 => "Only the first potion used in a room heals - any other potion in the same room is wasted."
+            .to_string(),
+        max => format!(
+            "Only the first {max} potions used in a room heal - any other potion in the same room is wasted."
+        ),
+    }
+}
+
+/// Joins the items into a list that reads like a sentence, e.g. "a, b and c".
+fn join(items: &[String]) -> String {
+    match items {
+        [] => String::new(),
+        [item] => item.clone(),
+        [rest @ .., last] => format!("{} and {last}", rest.join(", ")),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cards::deck::DeckVariant;
+    use crate::engine::rules::describe_rules;
+
+    #[test]
+    fn rules_should_match_the_deck_variant() {
+        let official = describe_rules(DeckVariant::Official);
+        let full = describe_rules(DeckVariant::Full);
+
+        assert_eq!(
+            official[0].paragraphs[0],
+            "You are a lone rogue in a dungeon of 44 cards - the Official deck. The Jack, Queen, King and Ace of Diamonds and Hearts are left out."
+        );
+        assert!(full[0].paragraphs[0].ends_with("Every card of the deck is used."));
+        assert!(official[1].paragraphs[1].contains("2 to 10."));
+        assert!(full[1].paragraphs[1].contains("2 to 14 (Jack 11"));
+    }
+}
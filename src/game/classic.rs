@@ -0,0 +1,167 @@
+use crate::engine::action::Action;
+use crate::engine::engine::{Engine, Phase};
+use crate::game::choice::Choice;
+use crossterm::cursor::MoveTo;
+use crossterm::terminal::{Clear, ClearType};
+use crossterm::{
+    QueueableCommand,
+    event::read,
+    terminal::{disable_raw_mode, enable_raw_mode},
+};
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::io::{Write, stdout};
+
+#[derive(Debug)]
+pub struct GameScore(Option<i32>);
+
+impl Display for GameScore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            Some(score) => write!(f, "Score: {score}"),
+            None => write!(f, ""),
+        }
+    }
+}
+
+/// A line-based interface to the engine, which plays the same game as the terminal UI without drawing it.
+pub struct Game {
+    engine: Engine,
+}
+
+impl Game {
+    pub fn new(engine: Engine) -> Self {
+        Self { engine }
+    }
+
+    /// returns the score at the end of the game
+    pub fn start_game(&mut self) -> io::Result<GameScore> {
+        let mut message: Option<String> = None;
+
+        loop {
+            if self.engine.is_over() {
+                return self.end_game();
+            }
+
+            /* display user stats and hand */
+            self.clear_screen()?;
+            self.show_stats();
+            self.show_hand();
+            if let Some(message) = message.take() {
+                println!("==> {message}\n");
+            }
+
+            let choice = match self.read_user_input() {
+                Ok(choice) => choice,
+                Err(error) if error.kind() == io::ErrorKind::InvalidInput => {
+                    message = Some(error.to_string());
+                    continue; // continue to read user input until a valid input is received
+                }
+                Err(error) => return Err(error),
+            };
+
+            let action = match choice {
+                Choice::Exit => return self.exit_game(),
+                Choice::Run => Action::AvoidRoom,
+                Choice::Card(card_number) => {
+                    // picking a card from a room that was dealt enters it
+                    if self.engine.phase() == Phase::BeforeRoom
+                        && let Err(error) = self.engine.apply(Action::EnterRoom)
+                    {
+                        message = Some(error.to_string());
+                        continue;
+                    }
+                    Action::PickCard(card_number as usize)
+                }
+                Choice::FightWithWeapon(true) => Action::FightWithWeapon,
+                Choice::FightWithWeapon(false) => Action::FightBareKnuckle,
+            };
+
+            if let Err(error) = self.engine.apply(action) {
+                message = Some(error.to_string());
+            }
+        }
+    }
+
+    fn read_user_input(&self) -> io::Result<Choice> {
+        // show prompt to user
+        if self.engine.phase() == Phase::ChooseWeaponOrBareKnuckle {
+            println!("Use weapon? [y/n]");
+        } else {
+            println!("Enter the card number [1-4] to select it - to quit the game, enter q:");
+            if self.engine.legal_actions().contains(&Action::AvoidRoom) {
+                println!("You may avoid the room by entering 0");
+            }
+        }
+
+        enable_raw_mode()?;
+        loop {
+            match read() {
+                Ok(event) => {
+                    if let Some(event) = event.as_key_event() {
+                        disable_raw_mode()?;
+                        return Choice::try_from(event)
+                            .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error));
+                    }
+                }
+                Err(error) => {
+                    disable_raw_mode()?;
+                    return Err(error);
+                }
+            }
+        }
+    }
+
+    fn show_hand(&self) {
+        println!("Your hand: ");
+        println!("{}\n", self.engine.hand());
+    }
+
+    fn clear_screen(&self) -> io::Result<()> {
+        let mut stdout = stdout();
+
+        // Queue the clear command
+        stdout.queue(Clear(ClearType::All))?;
+
+        // Optionally, move the cursor to the top-left position (0, 0)
+        stdout.queue(MoveTo(0, 0))?;
+
+        // Execute the queued commands by flushing the buffer
+        stdout.flush()
+    }
+
+    fn show_stats(&self) {
+        let border = "===========================================================================";
+        println!("{border}");
+        println!("Health: {}", self.engine.life());
+        if let Some(weapon) = self.engine.equipped_weapon() {
+            let blocked_creatures = self
+                .engine
+                .blocked_creatures()
+                .iter()
+                .map(|creature| creature.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            println!("Equipped weapon: {weapon}");
+            println!("Blocked creatures: {blocked_creatures}");
+        }
+        println!("{border}");
+    }
+
+    fn end_game(&self) -> io::Result<GameScore> {
+        self.clear_screen()?;
+        if self.engine.phase() == Phase::Lost {
+            println!("You died!\n");
+        } else {
+            println!("You survived!\n");
+        }
+
+        Ok(GameScore(Some(self.engine.calculate_score())))
+    }
+
+    fn exit_game(&self) -> io::Result<GameScore> {
+        self.clear_screen()?;
+        println!("Exiting the game...\n");
+        Ok(GameScore(None)) // return a game score of None as the user has exited the game
+    }
+}
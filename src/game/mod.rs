@@ -1,3 +1,4 @@
-pub mod game;
+pub mod classic;
 pub mod run_game;
+pub mod tutorial;
 mod choice;
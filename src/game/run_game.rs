@@ -1,139 +1,426 @@
-use crossterm::event::{read, Event, KeyCode, KeyEventKind};
+use chrono::Local;
+use crossterm::clipboard::CopyToClipboard;
+use crossterm::event::{poll, read, Event, KeyCode, KeyEvent, KeyEventKind};
+use crossterm::execute;
 use ratatui::DefaultTerminal;
-use crate::app::{App, CurrentScreen, HAND_SIZE};
+use crate::app::{App, CurrentScreen, MenuItem};
+use crate::engine::action::Action;
+use crate::engine::rule_error::RuleError;
+use crate::storage::history::RunRecord;
+use crate::storage::{daily, history, replay, save, summary};
 use crate::ui::ui;
+use std::io::stdout;
+use std::path::PathBuf;
+use std::time::Duration;
 
-pub fn run_game(terminal: &mut DefaultTerminal) -> color_eyre::Result<()> {
-    let mut app = App::default();
+/// How often the screen is redrawn while the comparison with the bot is computed.
+const COMPARISON_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+pub fn run_game(terminal: &mut DefaultTerminal, mut app: App) -> color_eyre::Result<()> {
+    app.has_saved_run = save::has_saved_run();
 
     loop {
+        app.receive_comparison();
         terminal.draw(|frame| ui(frame, &app))?;
 
-        match app.current_screen {
-            CurrentScreen::Welcome => {
-                if let Event::Key(key) = read()?
-                    && key.kind == KeyEventKind::Press
-                {
-                    match key.code {
-                        KeyCode::Char('q') => return Ok(()),
-                        _ => {
-                            app.current_screen = CurrentScreen::BeforeRoom;
-                            app.draw_cards(HAND_SIZE);
-                        }
+        // keep checking for the comparison with the bot, rather than waiting for a key
+        if app.is_comparing() && !poll(COMPARISON_POLL_INTERVAL)? {
+            continue;
+        }
+        if let Event::Key(key) = read()?
+            && key.kind == KeyEventKind::Press
+        {
+            let should_quit = match app.current_screen {
+                CurrentScreen::Menu => handle_menu_key(&mut app, key),
+                CurrentScreen::Exiting => match key.code {
+                    KeyCode::Char('y') => true,
+                    _ => {
+                        app.show_menu();
+                        false
                     }
+                },
+                CurrentScreen::ExitingRound => {
+                    handle_exiting_round_key(&mut app, key);
+                    false
+                }
+                CurrentScreen::PastRuns | CurrentScreen::Statistics => {
+                    handle_past_runs_key(&mut app, key);
+                    false
+                }
+                CurrentScreen::Replay => {
+                    handle_replay_key(&mut app, key);
+                    false
+                }
+                CurrentScreen::Settings => {
+                    handle_settings_key(&mut app, key);
+                    false
+                }
+                CurrentScreen::Rules => {
+                    handle_rules_key(&mut app, key);
+                    false
+                }
+                CurrentScreen::BeforeRoom
+                | CurrentScreen::ChooseCard
+                | CurrentScreen::ChooseWeaponOrBareKnuckle
+                | CurrentScreen::Won
+                | CurrentScreen::Lost
+                    if app.tutorial.is_some() =>
+                {
+                    handle_tutorial_key(&mut app, key);
+                    false
+                }
+                CurrentScreen::Won | CurrentScreen::Lost => {
+                    handle_end_of_run_key(&mut app, key);
+                    false
+                }
+                CurrentScreen::BeforeRoom
+                | CurrentScreen::ChooseCard
+                | CurrentScreen::ChooseWeaponOrBareKnuckle => {
+                    handle_run_key(&mut app, key);
+                    false
                 }
+            };
+
+            if should_quit {
+                return Ok(());
             }
+        }
+    }
+}
 
-            CurrentScreen::BeforeRoom => {
-                if app.has_avoided_room {
-                    app.current_screen = CurrentScreen::ChooseCard;
-                    continue;
+/// Returns true if the player has confirmed to quit the game.
+/// Files that cannot be read are reported in a notification, so that a broken file does not close the game.
+fn handle_menu_key(app: &mut App, key: KeyEvent) -> bool {
+    match key.code {
+        KeyCode::Up => app.move_menu_selection(false),
+        KeyCode::Down => app.move_menu_selection(true),
+        KeyCode::Char('q') => app.current_screen = CurrentScreen::Exiting,
+        KeyCode::Enter => match app.menu_selection {
+            MenuItem::NewRun => app.start_new_run(),
+            MenuItem::Continue => match save::load_run() {
+                Ok(Some(saved_run)) => app.resume_run(saved_run),
+                Ok(None) => app
+                    .notifications
+                    .push("There is no saved run to continue!".to_string()),
+                Err(error) => app
+                    .notifications
+                    .push(format!("Cannot continue your saved run: {error}")),
+            },
+            MenuItem::DailyChallenge => start_daily_challenge(app),
+            MenuItem::Tutorial => app.start_tutorial(),
+            MenuItem::PastRuns => match history::load_runs() {
+                Ok(past_runs) => app.show_past_runs(past_runs),
+                Err(error) => app
+                    .notifications
+                    .push(format!("Cannot read your past runs: {error}")),
+            },
+            MenuItem::Statistics => match history::load_runs() {
+                Ok(past_runs) => {
+                    app.show_past_runs(past_runs);
+                    app.current_screen = CurrentScreen::Statistics;
                 }
+                Err(error) => app
+                    .notifications
+                    .push(format!("Cannot read your past runs: {error}")),
+            },
+            MenuItem::Rules => app.show_rules(app.settings.variant),
+            MenuItem::Settings => app.current_screen = CurrentScreen::Settings,
+            MenuItem::Quit => app.current_screen = CurrentScreen::Exiting,
+        },
+        _ => {}
+    }
 
-                if let Event::Key(key) = read()?
-                    && key.kind == KeyEventKind::Press
-                {
-                    match key.code {
-                        // user chooses to enter room
-                        KeyCode::Char('y') => {
-                            app.current_screen = CurrentScreen::ChooseCard;
-                        }
+    false
+}
 
-                        // user avoids room
-                        KeyCode::Char('n') => {
-                            // draw new cards
-                            app.put_back_cards();
-                            app.draw_cards(HAND_SIZE);
-                            app.has_avoided_room = true;
+fn start_daily_challenge(app: &mut App) {
+    let date = daily::today();
+    match daily::has_attempted(date) {
+        Ok(true) => app.notifications.push(
+            "You already played today's daily challenge - come back tomorrow!".to_string(),
+        ),
+        // the attempt counts as soon as it is started, even if the run is abandoned
+        Ok(false) => match daily::record_attempt(date) {
+            Ok(()) => app.start_daily_run(date),
+            Err(error) => app
+                .notifications
+                .push(format!("Cannot record your daily challenge: {error}")),
+        },
+        Err(error) => app
+            .notifications
+            .push(format!("Cannot read your daily challenge: {error}")),
+    }
+}
 
-                            app.current_screen = CurrentScreen::BeforeRoom;
-                        }
+/// Files that cannot be written are reported in a notification, like in the menu.
+fn handle_exiting_round_key(app: &mut App, key: KeyEvent) {
+    match key.code {
+        // keep the run, so that it can be continued later
+        KeyCode::Char('s') => match save::save_run(app) {
+            Ok(()) => {
+                app.has_saved_run = true;
+                app.is_saved_run = true;
+                app.show_menu();
+            }
+            // the run is not lost, the player can keep playing it
+            Err(error) => {
+                app.notifications
+                    .push(format!("Cannot save your run: {error}"));
+                app.return_to_run();
+            }
+        },
+        KeyCode::Char('a') => {
+            forget_saved_run(app);
+            app.show_menu();
+        }
+        KeyCode::Esc | KeyCode::Char('n') => app.return_to_run(),
+        _ => {}
+    }
+}
 
-                        KeyCode::Char('q') => {
-                            return Ok(());
-                        }
+/// Deletes the save once the run that was saved is over or abandoned - the save of another run is kept.
+fn forget_saved_run(app: &mut App) {
+    if !app.is_saved_run {
+        return;
+    }
+    match save::delete_saved_run() {
+        Ok(()) => {
+            app.has_saved_run = false;
+            app.is_saved_run = false;
+        }
+        Err(error) => app
+            .notifications
+            .push(format!("Cannot delete your saved run: {error}")),
+    }
+}
 
-                        _ => {}
+fn handle_past_runs_key(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Char('s') => app.sort_past_runs(),
+        KeyCode::Enter if app.current_screen == CurrentScreen::PastRuns => {
+            let replay_file = app
+                .past_runs
+                .get(app.past_runs_scroll)
+                .and_then(|run| run.replay_file.clone());
+            match replay_file {
+                Some(replay_file) => match replay::load_replay(&replay_file) {
+                    Ok(replay) => {
+                        if let Err(error) = app.show_replay(replay) {
+                            app.notifications
+                                .push(format!("The replay of this run is broken: {error}"));
+                        }
                     }
-                }
+                    Err(error) => app
+                        .notifications
+                        .push(format!("Cannot read the replay of this run: {error}")),
+                },
+                None => app
+                    .notifications
+                    .push("There is no replay of this run".to_string()),
             }
+        }
+        KeyCode::Down => {
+            app.past_runs_scroll =
+                (app.past_runs_scroll + 1).min(app.past_runs.len().saturating_sub(1))
+        }
+        KeyCode::Up => app.past_runs_scroll = app.past_runs_scroll.saturating_sub(1),
+        KeyCode::Esc | KeyCode::Char('q') => app.show_menu(),
+        _ => {}
+    }
+}
 
-            CurrentScreen::ChooseCard => {
-                if let Event::Key(key) = read()?
-                    && key.kind == KeyEventKind::Press
-                {
-                    match key.code {
-                        KeyCode::Char(c @ ('1' | '2' | '3' | '4')) => {
-                            // safe to use unwrap, and to cast to usize, as we know the input is always 1, 2, 3 or 4
-                            let idx = c.to_digit(10).unwrap() as usize;
-                            if let Some(card) = app.select_card(idx) {
-                                match app.handle_card(card) {
-                                    Ok(Some(next_screen)) => app.current_screen = next_screen,
-                                    Err(error) => {
-                                        app.notifications.push(error.to_string());
-                                    }
-                                    _ => {}
-                                }
-                            } else {
-                                app.notifications
-                                    .push("Card has already been used!".to_string());
-                            }
-                        }
-                        KeyCode::Char('q') => {
-                            return Ok(());
-                        }
-                        _ => {}
-                    }
-                }
+fn handle_replay_key(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Right => app.go_to_replay_step(app.replay_step + 1),
+        KeyCode::Left => app.go_to_replay_step(app.replay_step.saturating_sub(1)),
+        KeyCode::Home => app.go_to_replay_step(0),
+        KeyCode::End => app.go_to_replay_step(usize::MAX),
+        KeyCode::Esc | KeyCode::Char('q') => app.current_screen = CurrentScreen::PastRuns,
+        _ => {}
+    }
+}
 
-                // TODO: refactor this: this is not good design, as we should not have to care about what the current screen is
-                if app.current_screen != CurrentScreen::ChooseWeaponOrBareKnuckle && app.current_screen != CurrentScreen::Lost {
-                    draw_cards_or_end_game(&mut app);
-                }
+fn handle_settings_key(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Up => app.move_settings_selection(false),
+        KeyCode::Down => app.move_settings_selection(true),
+        KeyCode::Enter => app.change_selected_setting(),
+        KeyCode::Esc | KeyCode::Char('q') => app.show_menu(),
+        _ => {}
+    }
+}
+
+fn handle_rules_key(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Up => app.scroll_rules(false),
+        KeyCode::Down => app.scroll_rules(true),
+        KeyCode::Esc | KeyCode::Char('q' | '?') => app.close_rules(),
+        _ => {}
+    }
+}
+
+/// Files that cannot be written are reported in a notification, like in the menu.
+fn handle_end_of_run_key(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Down => {
+            app.bot_moves_scroll =
+                (app.bot_moves_scroll + 1).min(app.bot_moves.len().saturating_sub(1))
+        }
+        KeyCode::Up => app.bot_moves_scroll = app.bot_moves_scroll.saturating_sub(1),
+        KeyCode::Char('c') => match copy_summary(app) {
+            Ok(()) => app.notifications.push(
+                "Copied the summary of your run (if your terminal allows copying to the clipboard)"
+                    .to_string(),
+            ),
+            Err(error) => app
+                .notifications
+                .push(format!("Cannot copy the summary of your run: {error}")),
+        },
+        KeyCode::Char('w') => match write_summary(app) {
+            Ok(path) => app.notifications.push(format!(
+                "Wrote the summary of your run to {}",
+                path.display()
+            )),
+            Err(error) => app
+                .notifications
+                .push(format!("Cannot write the summary of your run: {error}")),
+        },
+        KeyCode::Enter | KeyCode::Esc => {
+            if let Err(error) = record_run(app) {
+                app.notifications
+                    .push(format!("Cannot add your run to your past runs: {error}"));
             }
+            forget_saved_run(app);
+            app.show_menu();
+        }
+        _ => {}
+    }
+}
 
-            CurrentScreen::ChooseWeaponOrBareKnuckle => {
-                if let Event::Key(key) = read()?
-                    && key.kind == KeyEventKind::Press
-                {
-                    let next_screen = match key.code {
-                        KeyCode::Char('y') => app.fight_creature_with_weapon(),
+fn copy_summary(app: &App) -> color_eyre::Result<()> {
+    let summary = summary::run_summary(&app.engine.replay(), app.daily)?;
+    // the terminal cannot tell whether it copied the text, so this is only a best effort
+    execute!(stdout(), CopyToClipboard::to_clipboard_from(summary))?;
 
-                        KeyCode::Char('n') => app.fight_creature_bare_knuckle(),
+    Ok(())
+}
 
-                        _ => Ok(None),
-                    };
+fn write_summary(app: &App) -> color_eyre::Result<PathBuf> {
+    let replay = app.engine.replay();
+    let summary = summary::run_summary(&replay, app.daily)?;
+    summary::save_summary(&summary, &replay, Local::now())
+}
 
-                    match next_screen {
-                        Ok(Some(next_screen)) => {
-                            app.current_screen = next_screen;
-                        }
-                        Err(error) => {
-                            app.notifications.push(error.to_string());
-                        }
-                        _ => {
-                            // default transition
-                            draw_cards_or_end_game(&mut app);
-                        }
-                    }
-                }
+/// Adds the finished run to the past runs, with its replay.
+fn record_run(app: &App) -> color_eyre::Result<()> {
+    if let Some(mut record) = RunRecord::from_finished_run(app) {
+        record.replay_file = Some(replay::save_replay(
+            &app.engine.replay(),
+            record.finished_at,
+        )?);
+        history::append_run(&record)?;
+    }
+
+    Ok(())
+}
+
+fn handle_run_key(app: &mut App, key: KeyEvent) {
+    let action = match (app.current_screen, key.code) {
+        (_, KeyCode::Char('q')) => {
+            app.current_screen = CurrentScreen::ExitingRound;
+            None
+        }
+        (_, KeyCode::Char('u')) => {
+            if !app.undo() {
+                app.notifications.push("Nothing to undo!".to_string());
+            }
+            None
+        }
+        (_, KeyCode::Char('h')) => {
+            app.toggle_hints();
+            None
+        }
+        // the rules of the run being played, which may not be the variant in the settings
+        (_, KeyCode::Char('?')) => {
+            app.show_rules(app.engine.deck().variant());
+            None
+        }
+        // like hints, the forecast would make the daily challenge unfair
+        (_, KeyCode::Char('f')) if app.daily.is_some() => {
+            app.notifications
+                .push("There is no forecast in the daily challenge".to_string());
+            None
+        }
+        (_, KeyCode::Char('f')) => {
+            if !app.toggle_forecast() {
+                app.notifications
+                    .push("The forecast is only shown before entering a room".to_string());
             }
-            _ => {}
+            None
+        }
+        (_, KeyCode::Char('r')) => {
+            if !app.redo() {
+                app.notifications.push("Nothing to redo!".to_string());
+            }
+            None
+        }
+
+        _ => action_for_key(app.current_screen, key.code),
+    };
+
+    if let Some(action) = action {
+        match app.apply(action) {
+            Ok(()) => {}
+            Err(RuleError::GameAlreadyOver) => app.return_to_run(),
+            Err(error) => app.notifications.push(error.to_string()),
         }
     }
 }
 
-fn draw_cards_or_end_game(app: &mut App) {
-    if app.hand.num_cards_remaining() == 1 {
-        app.has_avoided_room = false;
-        let enough_cards_left_in_deck = app.draw_cards(HAND_SIZE);
-        if !enough_cards_left_in_deck {
-            // end the game if there are not enough cards remaining to play the game
-            app.current_screen = CurrentScreen::Won;
-        } else {
-            app.current_screen = CurrentScreen::BeforeRoom;
+/// Only lets the player make the move that the tutorial teaches.
+fn handle_tutorial_key(app: &mut App, key: KeyEvent) {
+    let action = match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app.leave_tutorial();
+            return;
         }
-    } else {
-        app.current_screen = CurrentScreen::ChooseCard;
+        KeyCode::Enter => None,
+        code => match action_for_key(app.current_screen, code) {
+            Some(action) => Some(action),
+            None => return,
+        },
+    };
+
+    match app.follow_tutorial(action) {
+        Ok(true) => {}
+        Ok(false) => app
+            .notifications
+            .push("That is not what the tutorial asks for - read the explanation below".to_string()),
+        Err(error) => app.notifications.push(error.to_string()),
     }
-}
\ No newline at end of file
+}
+
+/// Returns the move that a key stands for, in the screen of the run.
+fn action_for_key(screen: CurrentScreen, code: KeyCode) -> Option<Action> {
+    match (screen, code) {
+        // user chooses to enter room
+        (CurrentScreen::BeforeRoom, KeyCode::Char('y')) => Some(Action::EnterRoom),
+        // user avoids room
+        (CurrentScreen::BeforeRoom, KeyCode::Char('n')) => Some(Action::AvoidRoom),
+
+        (CurrentScreen::ChooseCard, KeyCode::Char(c @ ('1' | '2' | '3' | '4'))) => {
+            // safe to use unwrap, and to cast to usize, as we know the input is always 1, 2, 3 or 4
+            let card_num = c.to_digit(10).unwrap() as usize;
+            Some(Action::PickCard(card_num))
+        }
+
+        (CurrentScreen::ChooseWeaponOrBareKnuckle, KeyCode::Char('y')) => {
+            Some(Action::FightWithWeapon)
+        }
+        (CurrentScreen::ChooseWeaponOrBareKnuckle, KeyCode::Char('n')) => {
+            Some(Action::FightBareKnuckle)
+        }
+
+        _ => None,
+    }
+}
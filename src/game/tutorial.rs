@@ -0,0 +1,249 @@
+use crate::cards::deck::{Card, Deck, DeckVariant, Rank, Suite};
+use crate::engine::action::Action;
+use crate::engine::engine::Engine;
+
+/// The dungeon of the tutorial, from the top - every room is dealt to teach a rule.
+const DECK: [Card; 14] = [
+    // room 1: a monster with no weapon, then two potions in the same room
+    card(Suite::Club, Rank::Five),
+    card(Suite::Heart, Rank::Four),
+    card(Suite::Heart, Rank::Three),
+    card(Suite::Diamond, Rank::Seven),
+    // room 2: a weapon, used on creatures from the strongest to the weakest
+    card(Suite::Spade, Rank::Six),
+    card(Suite::Club, Rank::Four),
+    card(Suite::Spade, Rank::Nine),
+    // room 3: creatures that the weapon cannot block any more, so the room is fled
+    card(Suite::Club, Rank::Eight),
+    card(Suite::Heart, Rank::Six),
+    card(Suite::Spade, Rank::Ten),
+    // room 4: a small creature fought bare-knuckle, a new weapon, and a potion
+    card(Suite::Club, Rank::Three),
+    card(Suite::Club, Rank::Two),
+    card(Suite::Diamond, Rank::Nine),
+    card(Suite::Heart, Rank::Five),
+    // the fled room comes back at the end of the dungeon
+];
+
+const fn card(suite: Suite, rank: Rank) -> Card {
+    Card { suite, rank }
+}
+
+/// What the player has to do to move on to the next step.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Lesson {
+    /// Read the explanation, and press enter.
+    Read,
+    /// Take this action.
+    Take(Action),
+    /// Pick this card from the room, wherever it is in the room.
+    Pick(Card),
+}
+
+/// A step of the tutorial: an explanation, and the only thing the player is allowed to do.
+#[derive(Clone, Copy, Debug)]
+pub struct Step {
+    pub explanation: &'static str,
+    pub lesson: Lesson,
+}
+
+const fn read(explanation: &'static str) -> Step {
+    Step {
+        explanation,
+        lesson: Lesson::Read,
+    }
+}
+
+const fn take(action: Action, explanation: &'static str) -> Step {
+    Step {
+        explanation,
+        lesson: Lesson::Take(action),
+    }
+}
+
+const fn pick(suite: Suite, rank: Rank, explanation: &'static str) -> Step {
+    Step {
+        explanation,
+        lesson: Lesson::Pick(card(suite, rank)),
+    }
+}
+
+const STEPS: &[Step] = &[
+    read(
+        "Welcome to Scoundrel! You are about to descend into a dungeon of 14 cards. Clubs and Spades are monsters, Diamonds are weapons and Hearts are potions - each is as strong as its rank.",
+    ),
+    read(
+        "Cards are dealt 4 at a time into a room. You play 3 of them, and the last one stays for the next room. You start with 20 life, and the dungeon is cleared once every card was dealt.",
+    ),
+    take(
+        Action::EnterRoom,
+        "Before a room, you choose to enter it or to flee. This room looks safe enough - enter it.",
+    ),
+    pick(
+        Suite::Club,
+        Rank::Five,
+        "Without a weapon, monsters are fought bare-knuckle: you lose as much life as the monster is strong. Fight the 5 of Clubs.",
+    ),
+    pick(
+        Suite::Heart,
+        Rank::Four,
+        "Potions heal you by their rank, up to 20 life. Drink the 4 of Hearts.",
+    ),
+    pick(
+        Suite::Heart,
+        Rank::Three,
+        "Only the first potion of a room heals - any other potion in the same room is wasted. Drink the 3 of Hearts to see it.",
+    ),
+    take(
+        Action::EnterRoom,
+        "The 7 of Diamonds stayed for the next room, which was dealt around it. Enter the room.",
+    ),
+    pick(
+        Suite::Diamond,
+        Rank::Seven,
+        "Weapons are equipped when they are picked, replacing the weapon you had. Equip the 7 of Diamonds.",
+    ),
+    pick(
+        Suite::Spade,
+        Rank::Six,
+        "With a weapon, a monster only deals the damage that is left after the weapon's strength. Pick the 6 of Spades.",
+    ),
+    take(
+        Action::FightWithWeapon,
+        "You can fight with the weapon, or bare-knuckle. Your 7 blocks all of the 6 - fight with the weapon.",
+    ),
+    pick(
+        Suite::Club,
+        Rank::Four,
+        "A weapon dulls: after blocking a monster, it can only be used on monsters weaker than that one. The 4 of Clubs is weaker than the 6 - pick it.",
+    ),
+    take(
+        Action::FightWithWeapon,
+        "Fight it with the weapon. Your weapon can now only block monsters weaker than 4, so block the strongest monsters first!",
+    ),
+    take(
+        Action::AvoidRoom,
+        "This room holds a 9 and a 10, which your weapon can no longer block. Flee: its cards go to the bottom of the dungeon, and a new room is dealt - which you have to enter, as you cannot flee twice in a row.",
+    ),
+    pick(
+        Suite::Club,
+        Rank::Two,
+        "Fighting bare-knuckle leaves your weapon as it is, which is worth it for weak monsters. Pick the 2 of Clubs.",
+    ),
+    take(
+        Action::FightBareKnuckle,
+        "Fight it bare-knuckle, and lose 2 life.",
+    ),
+    pick(
+        Suite::Diamond,
+        Rank::Nine,
+        "A new weapon can block any monster again. Equip the 9 of Diamonds.",
+    ),
+    pick(
+        Suite::Heart,
+        Rank::Five,
+        "Heal with the 5 of Hearts - you cannot go above 20 life.",
+    ),
+    take(
+        Action::EnterRoom,
+        "The monsters you fled from are back, along with the 3 of Clubs. Enter the room.",
+    ),
+    pick(
+        Suite::Spade,
+        Rank::Nine,
+        "Block the strongest monster first: pick the 9 of Spades.",
+    ),
+    take(Action::FightWithWeapon, "Fight it with the weapon."),
+    pick(
+        Suite::Club,
+        Rank::Eight,
+        "The 8 of Clubs is weaker than the 9, so your weapon can still block it. Pick it.",
+    ),
+    take(Action::FightWithWeapon, "Fight it with the weapon."),
+    pick(
+        Suite::Club,
+        Rank::Three,
+        "And the 3 of Clubs is weaker than the 8. Pick it.",
+    ),
+    take(Action::FightWithWeapon, "Fight it with the weapon."),
+    take(
+        Action::EnterRoom,
+        "The last room only holds the 2 cards left in the dungeon. Enter it.",
+    ),
+    pick(
+        Suite::Spade,
+        Rank::Ten,
+        "Your weapon last blocked the 3 of Clubs, so the 10 of Spades has to be fought bare-knuckle. Pick it, and keep the potion for last.",
+    ),
+    read(
+        "You cleared the dungeon! Your score is your life left, plus the 6 of Hearts, as a potion was the last card left. Had you died, your score would have been minus the strength of the monsters left in the dungeon.",
+    ),
+    read(
+        "That is all there is to it. The dungeon of a real run has 44 cards (or 52 with the full deck), so plan ahead, and good luck!",
+    ),
+];
+
+/// A game played with a dungeon made to teach the rules, one step at a time.
+#[derive(Clone, Debug, Default)]
+pub struct Tutorial {
+    step: usize,
+}
+
+impl Tutorial {
+    /// Starts the game of the tutorial.
+    pub fn start_engine() -> Engine {
+        Engine::with_deck(0, Deck::from_cards(DeckVariant::Official, &DECK))
+    }
+
+    /// Returns None once the tutorial is finished.
+    pub fn current_step(&self) -> Option<&'static Step> {
+        STEPS.get(self.step)
+    }
+
+    /// Returns the number of the current step, and the number of steps.
+    pub fn progress(&self) -> (usize, usize) {
+        (self.step + 1, STEPS.len())
+    }
+
+    /// Returns the action that the current step teaches, if it teaches one.
+    pub fn expected_action(&self, engine: &Engine) -> Option<Action> {
+        match self.current_step()?.lesson {
+            Lesson::Read => None,
+            Lesson::Take(action) => Some(action),
+            Lesson::Pick(card) => engine
+                .hand()
+                .iter()
+                .position(|slot| *slot == Some(card))
+                .map(|idx| Action::PickCard(idx + 1)),
+        }
+    }
+
+    pub fn next_step(&mut self) {
+        self.step += 1;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::engine::engine::Phase;
+    use crate::game::tutorial::{Lesson, Tutorial};
+
+    #[test]
+    fn tutorial_should_clear_the_dungeon_when_followed() {
+        let mut tutorial = Tutorial::default();
+        let mut engine = Tutorial::start_engine();
+
+        while let Some(step) = tutorial.current_step() {
+            if step.lesson != Lesson::Read {
+                let action = tutorial
+                    .expected_action(&engine)
+                    .unwrap_or_else(|| panic!("{:?} cannot be taken", step.lesson));
+                engine.apply(action).unwrap();
+            }
+            tutorial.next_step();
+        }
+
+        assert_eq!(engine.phase(), Phase::Won);
+        assert_eq!(engine.calculate_score(), 16);
+    }
+}
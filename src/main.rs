@@ -2,14 +2,147 @@
 
 extern crate core;
 
+use crate::app::{App, Settings};
+use crate::bot::simulate::simulate;
+use crate::bot::solver::solve;
+use crate::bot::strategy::StrategyKind;
+use crate::cards::deck::{Deck, DeckVariant, Seed};
+use crate::engine::engine::Engine;
+use crate::engine::replay::Replay;
+use crate::game::classic::Game;
 use crate::game::run_game::run_game;
+use crate::storage::history;
+use crate::storage::history::RunStatistics;
+use crate::storage::replay::load_replay;
+use crate::ui::{NO_RUNS_YET, Theme};
+use clap::{ArgGroup, Parser};
+use std::path::PathBuf;
 
 mod app;
+mod bot;
 mod cards;
+mod engine;
 mod game;
+mod storage;
 mod ui;
 
+/// Play Scoundrel on your CLI.
+///
+/// Without options, the game starts in the menu. The other modes print their results instead,
+/// so that they can be used in scripts.
+#[derive(Parser)]
+#[command(version, about, long_about = None)]
+#[command(group(ArgGroup::new("mode").args(["replay", "stats", "simulate", "solve", "classic"])))]
+struct Cli {
+    /// Seed of the deck - pass the seed shown in-game to play the same run again.
+    /// With --simulate, the seed of the first game
+    #[arg(long)]
+    seed: Option<Seed>,
+
+    /// Cards that make up the dungeon
+    #[arg(long, value_enum)]
+    variant: Option<DeckVariant>,
+
+    /// Play an ironman run, where moves cannot be undone
+    #[arg(long)]
+    no_undo: bool,
+
+    /// Colours of the interface
+    #[arg(long, value_enum, default_value_t)]
+    theme: Theme,
+
+    /// Watch the replay of a past run, from a file in the replays directory
+    #[arg(long, value_name = "FILE")]
+    replay: Option<PathBuf>,
+
+    /// Print the statistics of past runs
+    #[arg(long)]
+    stats: bool,
+
+    /// Let a bot play N games, one seed after the other, and print how well it did
+    #[arg(long, value_name = "N")]
+    simulate: Option<usize>,
+
+    /// Strategy of the bot that plays the simulated games
+    #[arg(long, value_enum, default_value_t = StrategyKind::FirstLegalAction, requires = "simulate")]
+    bot: StrategyKind,
+
+    /// Find the best possible score of the seed, and print the moves that lead to it
+    #[arg(long, requires = "seed")]
+    solve: bool,
+
+    /// Play with the line-based interface, which is driven by the same engine but has neither undo nor themes
+    #[arg(long, conflicts_with_all = ["no_undo", "theme"])]
+    classic: bool,
+}
+
 fn main() -> color_eyre::Result<()> {
-    ratatui::run(run_game)?;
+    let cli = Cli::parse();
+    let variant = cli.variant.unwrap_or_default();
+
+    if cli.stats {
+        return print_statistics();
+    }
+    if let Some(games) = cli.simulate {
+        print!("{}", simulate(cli.bot, variant, games, cli.seed.unwrap_or(0))?);
+        return Ok(());
+    }
+    if cli.solve
+        && let Some(seed) = cli.seed
+    {
+        return run_solver(variant, seed);
+    }
+    if cli.classic {
+        return run_classic_game(variant, cli.seed.unwrap_or_else(Deck::random_seed));
+    }
+
+    let settings = Settings {
+        variant,
+        allow_undo: !cli.no_undo,
+    };
+    let mut app = App::new(settings, cli.seed);
+    app.theme = cli.theme;
+    if let Some(path) = &cli.replay {
+        app.show_past_runs(history::load_runs()?);
+        app.show_replay(load_replay(path)?)?;
+    }
+
+    ratatui::run(|terminal| run_game(terminal, app))?;
+    Ok(())
+}
+
+fn print_statistics() -> color_eyre::Result<()> {
+    match RunStatistics::from_runs(&history::load_runs()?) {
+        Some(statistics) => println!("{statistics}"),
+        None => println!("{NO_RUNS_YET}"),
+    }
+
+    Ok(())
+}
+
+fn run_solver(variant: DeckVariant, seed: Seed) -> color_eyre::Result<()> {
+    let solution = solve(variant, seed);
+    println!(
+        "Seed {seed}, deck: {variant} - the best possible score is {} ({} positions searched)",
+        solution.score, solution.positions_searched
+    );
+
+    let replay = Replay {
+        variant,
+        seed,
+        actions: solution.actions,
+    };
+    for description in replay.describe_moves()? {
+        println!("{description}");
+    }
+
+    Ok(())
+}
+
+/// Plays with the line-based interface - see `Game`.
+fn run_classic_game(variant: DeckVariant, seed: Seed) -> color_eyre::Result<()> {
+    let score = Game::new(Engine::new(variant, seed)).start_game()?;
+    println!("{score}");
+
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,84 @@
+//! The daily challenge: one run a day on a deck seeded from the date, so that everyone plays the same dungeon.
+use crate::app::Settings;
+use crate::cards::deck::{DeckVariant, Seed};
+use crate::engine::engine::Engine;
+use crate::storage::data_dir;
+use crate::storage::summary::describe_outcome;
+use chrono::{Datelike, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+const DAILY_FILE_NAME: &str = "daily.json";
+
+/// Daily runs are the same for everyone: the official deck, and no undo.
+pub const DAILY_SETTINGS: Settings = Settings {
+    variant: DeckVariant::Official,
+    allow_undo: false,
+};
+
+/// The last daily challenge that was started - an attempt counts as soon as it is started,
+/// so that abandoning a bad dungeon does not give another try.
+#[derive(Serialize, Deserialize)]
+struct DailyAttempt {
+    date: NaiveDate,
+}
+
+/// Returns the date of the daily challenge that can be played now.
+pub fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+/// Derives the seed of the daily challenge from the date.
+/// This must give the same seed on every machine and in every version, so it does not use the
+/// standard library's hasher, which is allowed to change between releases.
+pub fn daily_seed(date: NaiveDate) -> Seed {
+    // splitmix64, which spreads consecutive days over unrelated seeds
+    let mut z = (date.num_days_from_ce() as u64).wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Returns the text that the player can share to compare their daily run with others.
+pub fn share_result(date: NaiveDate, engine: &Engine) -> String {
+    format!("Scoundrel daily {date}: {}", describe_outcome(engine))
+}
+
+fn daily_file_path() -> color_eyre::Result<PathBuf> {
+    Ok(data_dir()?.join(DAILY_FILE_NAME))
+}
+
+/// Returns true if the daily challenge of the date was already started.
+pub fn has_attempted(date: NaiveDate) -> color_eyre::Result<bool> {
+    match fs::read_to_string(daily_file_path()?) {
+        Ok(contents) => Ok(serde_json::from_str::<DailyAttempt>(&contents)?.date == date),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(false),
+        Err(error) => Err(error.into()),
+    }
+}
+
+/// Records that the daily challenge of the date was started.
+pub fn record_attempt(date: NaiveDate) -> color_eyre::Result<()> {
+    fs::write(daily_file_path()?, serde_json::to_string(&DailyAttempt { date })?)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::storage::daily::daily_seed;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn daily_seed_should_only_depend_on_the_date() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 14).unwrap();
+        let next_day = date.succ_opt().unwrap();
+
+        assert_eq!(daily_seed(date), daily_seed(date));
+        assert_ne!(daily_seed(date), daily_seed(next_day));
+        // the seed of a date must never change, or old daily results could not be compared
+        assert_eq!(daily_seed(date), 10159671307478127391);
+    }
+}
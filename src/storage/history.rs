@@ -0,0 +1,232 @@
+use crate::app::App;
+use crate::cards::deck::{DeckVariant, Seed};
+use crate::engine::engine::Phase;
+use crate::storage::data_dir;
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::fs;
+use std::fs::OpenOptions;
+use std::io::{ErrorKind, Write};
+use std::path::PathBuf;
+
+const HISTORY_FILE_NAME: &str = "history.jsonl"; // one run per line, so that runs can be appended
+
+/// The result of a finished run.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct RunRecord {
+    pub finished_at: DateTime<Local>,
+    pub seed: Seed,
+    pub variant: DeckVariant,
+    pub score: i32,
+    pub won: bool,
+    pub rooms_cleared: u32,
+    pub turns: u32,
+    pub damage_taken: u32,
+    pub potions_used: u32,
+    #[serde(default)]
+    pub used_undo: bool,
+    #[serde(default)]
+    pub replay_file: Option<PathBuf>, // runs recorded before replays were added have none
+    #[serde(default)]
+    pub daily: Option<NaiveDate>, // date of the daily challenge, if the run was one
+}
+
+impl RunRecord {
+    /// Records the result of the run - returns None if the run is not over yet.
+    /// The replay file is left empty, as it is written separately.
+    pub fn from_finished_run(app: &App) -> Option<RunRecord> {
+        let won = match app.engine.phase() {
+            Phase::Won => true,
+            Phase::Lost => false,
+            _ => return None,
+        };
+        let stats = app.engine.stats();
+
+        Some(RunRecord {
+            finished_at: Local::now(),
+            seed: app.engine.seed(),
+            variant: app.engine.deck().variant(),
+            score: app.engine.calculate_score(),
+            won,
+            rooms_cleared: stats.rooms_cleared,
+            turns: stats.turns,
+            damage_taken: stats.damage_taken,
+            potions_used: stats.potions_used,
+            used_undo: app.has_used_undo,
+            replay_file: None,
+            daily: app.daily,
+        })
+    }
+}
+
+/// The order in which past runs are listed.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum SortRunsBy {
+    #[default]
+    MostRecent,
+    HighestScore,
+    MostRoomsCleared,
+}
+
+impl SortRunsBy {
+    pub fn next(&self) -> SortRunsBy {
+        match self {
+            SortRunsBy::MostRecent => SortRunsBy::HighestScore,
+            SortRunsBy::HighestScore => SortRunsBy::MostRoomsCleared,
+            SortRunsBy::MostRoomsCleared => SortRunsBy::MostRecent,
+        }
+    }
+
+    pub fn sort(&self, runs: &mut [RunRecord]) {
+        match self {
+            SortRunsBy::MostRecent => runs.sort_by_key(|run| Reverse(run.finished_at)),
+            SortRunsBy::HighestScore => runs.sort_by_key(|run| Reverse(run.score)),
+            SortRunsBy::MostRoomsCleared => runs.sort_by_key(|run| Reverse(run.rooms_cleared)),
+        }
+    }
+}
+
+impl std::fmt::Display for SortRunsBy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SortRunsBy::MostRecent => write!(f, "most recent"),
+            SortRunsBy::HighestScore => write!(f, "highest score"),
+            SortRunsBy::MostRoomsCleared => write!(f, "most rooms cleared"),
+        }
+    }
+}
+
+/// Returns the daily challenge runs, best score first.
+pub fn daily_leaderboard(runs: &[RunRecord]) -> Vec<&RunRecord> {
+    let mut daily_runs: Vec<&RunRecord> = runs.iter().filter(|run| run.daily.is_some()).collect();
+    daily_runs.sort_by_key(|run| (Reverse(run.score), run.daily));
+
+    daily_runs
+}
+
+/// Totals and averages over past runs.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct RunStatistics {
+    pub runs_played: usize,
+    pub runs_won: usize,
+    pub best_score: i32,
+    pub average_score: f64,
+    pub average_rooms_cleared: f64,
+    pub total_damage_taken: u32,
+    pub total_potions_used: u32,
+}
+
+impl RunStatistics {
+    /// Returns None if there are no runs.
+    pub fn from_runs(runs: &[RunRecord]) -> Option<RunStatistics> {
+        let best_score = runs.iter().map(|run| run.score).max()?;
+        let runs_played = runs.len();
+
+        Some(RunStatistics {
+            runs_played,
+            runs_won: runs.iter().filter(|run| run.won).count(),
+            best_score,
+            average_score: runs.iter().map(|run| run.score as f64).sum::<f64>()
+                / runs_played as f64,
+            average_rooms_cleared: runs.iter().map(|run| run.rooms_cleared as f64).sum::<f64>()
+                / runs_played as f64,
+            total_damage_taken: runs.iter().map(|run| run.damage_taken).sum(),
+            total_potions_used: runs.iter().map(|run| run.potions_used).sum(),
+        })
+    }
+
+    pub fn win_rate(&self) -> f64 {
+        self.runs_won as f64 / self.runs_played as f64
+    }
+}
+
+impl std::fmt::Display for RunStatistics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Runs played: {}", self.runs_played)?;
+        writeln!(
+            f,
+            "Runs won: {} ({:.0}%)",
+            self.runs_won,
+            self.win_rate() * 100.0
+        )?;
+        writeln!(f, "Best score: {}", self.best_score)?;
+        writeln!(f, "Average score: {:.1}", self.average_score)?;
+        writeln!(f, "Average rooms cleared: {:.1}", self.average_rooms_cleared)?;
+        writeln!(f, "Total damage taken: {}", self.total_damage_taken)?;
+        writeln!(f, "Total potions used: {}", self.total_potions_used)
+    }
+}
+
+fn history_file_path() -> color_eyre::Result<PathBuf> {
+    Ok(data_dir()?.join(HISTORY_FILE_NAME))
+}
+
+/// Appends a finished run to the history.
+pub fn append_run(record: &RunRecord) -> color_eyre::Result<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(history_file_path()?)?;
+    writeln!(file, "{}", serde_json::to_string(record)?)?;
+
+    Ok(())
+}
+
+/// Reads all past runs, in the order they were finished.
+pub fn load_runs() -> color_eyre::Result<Vec<RunRecord>> {
+    match fs::read_to_string(history_file_path()?) {
+        Ok(contents) => Ok(parse_runs(&contents)),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(Vec::new()),
+        Err(error) => Err(error.into()),
+    }
+}
+
+/// Parses one run per line - lines that cannot be read are skipped, so that one bad line does not lose the whole history.
+fn parse_runs(contents: &str) -> Vec<RunRecord> {
+    contents
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cards::deck::DeckVariant;
+    use crate::storage::history::{RunRecord, SortRunsBy, parse_runs};
+    use chrono::Local;
+
+    #[test]
+    fn runs_should_be_parsed_one_per_line_skipping_bad_lines() {
+        let run = RunRecord {
+            finished_at: Local::now(),
+            seed: 42,
+            variant: DeckVariant::Official,
+            score: 12,
+            won: true,
+            rooms_cleared: 13,
+            turns: 40,
+            damage_taken: 60,
+            potions_used: 9,
+            used_undo: false,
+            replay_file: None,
+            daily: None,
+        };
+        let worse_run = RunRecord {
+            score: -30,
+            won: false,
+            ..run.clone()
+        };
+        let contents = format!(
+            "{}\nnot a run\n{}\n",
+            serde_json::to_string(&worse_run).unwrap(),
+            serde_json::to_string(&run).unwrap()
+        );
+
+        let mut runs = parse_runs(&contents);
+        assert_eq!(runs, vec![worse_run, run.clone()]);
+
+        SortRunsBy::HighestScore.sort(&mut runs);
+        assert_eq!(runs[0], run);
+    }
+}
@@ -0,0 +1,20 @@
+//! Files that Scoundrel keeps in the user's data directory.
+pub mod daily;
+pub mod history;
+pub mod replay;
+pub mod save;
+pub mod summary;
+
+use color_eyre::eyre::OptionExt;
+use std::fs;
+use std::path::PathBuf;
+
+/// Returns the directory where Scoundrel keeps its files, creating it if it does not exist yet.
+pub fn data_dir() -> color_eyre::Result<PathBuf> {
+    let dir = dirs::data_dir()
+        .ok_or_eyre("Cannot find the data directory of the user")?
+        .join("scoundrel");
+    fs::create_dir_all(&dir)?;
+
+    Ok(dir)
+}
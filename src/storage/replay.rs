@@ -0,0 +1,94 @@
+//! Replay files, one per finished run, kept in the `replays` directory.
+//!
+//! A replay file is a JSON object holding the version of the format, the deck variant and the
+//! seed that the deck was shuffled from, and the actions that the player took, in order:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "variant": "Official",
+//!   "seed": 42,
+//!   "actions": ["EnterRoom", {"PickCard": 1}, {"PickCard": 3}, {"PickCard": 2}]
+//! }
+//! ```
+//!
+//! The actions are `EnterRoom`, `AvoidRoom`, `{"PickCard": n}` (n is the card number, from 1 to 4),
+//! `FightWithWeapon` and `FightBareKnuckle`.
+use crate::engine::replay::Replay;
+use crate::storage::data_dir;
+use chrono::{DateTime, Local};
+use color_eyre::eyre::{OptionExt, bail};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Version of the replay file format - bump this (and read older versions in `parse_replay`)
+/// whenever the format changes, so that old replays can still be watched.
+pub const REPLAY_FORMAT_VERSION: u32 = 1;
+
+const REPLAYS_DIR_NAME: &str = "replays";
+
+/// A replay, as written to a replay file.
+#[derive(Serialize, Deserialize)]
+struct ReplayFile {
+    version: u32,
+    #[serde(flatten)]
+    replay: Replay,
+}
+
+/// Writes the replay of a run that finished at `finished_at`, and returns the path of the file.
+pub fn save_replay(replay: &Replay, finished_at: DateTime<Local>) -> color_eyre::Result<PathBuf> {
+    let dir = data_dir()?.join(REPLAYS_DIR_NAME);
+    fs::create_dir_all(&dir)?;
+
+    let path = dir.join(format!(
+        "{}-{}.json",
+        finished_at.format("%Y%m%d-%H%M%S"),
+        replay.seed
+    ));
+    let contents = serde_json::to_string_pretty(&ReplayFile {
+        version: REPLAY_FORMAT_VERSION,
+        replay: replay.clone(),
+    })?;
+    fs::write(&path, contents)?;
+
+    Ok(path)
+}
+
+pub fn load_replay(path: &Path) -> color_eyre::Result<Replay> {
+    parse_replay(&fs::read_to_string(path)?)
+}
+
+fn parse_replay(contents: &str) -> color_eyre::Result<Replay> {
+    let value: serde_json::Value = serde_json::from_str(contents)?;
+    let version = value
+        .get("version")
+        .and_then(|version| version.as_u64())
+        .ok_or_eyre("Replay file has no version")?;
+
+    match version {
+        1 => Ok(serde_json::from_value::<ReplayFile>(value)?.replay),
+        _ => bail!("Replay file version {version} is not supported by this version of Scoundrel"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cards::deck::DeckVariant;
+    use crate::engine::action::Action;
+    use crate::storage::replay::parse_replay;
+
+    #[test]
+    fn documented_format_should_be_parsed() {
+        let replay = parse_replay(
+            r#"{"version": 1, "variant": "Official", "seed": 42, "actions": ["EnterRoom", {"PickCard": 1}, {"PickCard": 3}, {"PickCard": 2}]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(replay.variant, DeckVariant::Official);
+        assert_eq!(replay.seed, 42);
+        assert_eq!(replay.actions[..2], [Action::EnterRoom, Action::PickCard(1)]);
+        assert_eq!(replay.play().unwrap().len(), 5);
+        assert!(parse_replay(r#"{"version": 999}"#).is_err());
+    }
+}
@@ -0,0 +1,146 @@
+use crate::app::{App, Settings};
+use crate::engine::engine::Engine;
+use crate::storage::data_dir;
+use chrono::NaiveDate;
+use color_eyre::eyre::{OptionExt, bail};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+/// Version of the save file format - bump this (and migrate older versions in `parse_saved_run`)
+/// whenever the format changes, so that saves survive upgrades.
+pub const SAVE_FORMAT_VERSION: u32 = 1;
+
+const SAVE_FILE_NAME: &str = "save.json";
+
+/// A run in progress, as written to the save file.
+#[derive(Serialize, Deserialize)]
+pub struct SavedRun {
+    pub version: u32,
+    pub settings: Settings,
+    pub has_used_undo: bool,
+    #[serde(default)]
+    pub daily: Option<NaiveDate>, // date of the daily challenge, if the run is one
+    pub engine: Engine, // holds the seed, deck order, hand slots, life, weapon, blocked creatures and flee flag
+}
+
+impl From<&App> for SavedRun {
+    fn from(app: &App) -> Self {
+        SavedRun {
+            version: SAVE_FORMAT_VERSION,
+            settings: app.run_settings,
+            has_used_undo: app.has_used_undo,
+            daily: app.daily,
+            engine: app.engine.clone(),
+        }
+    }
+}
+
+fn save_file_path() -> color_eyre::Result<PathBuf> {
+    Ok(data_dir()?.join(SAVE_FILE_NAME))
+}
+
+/// Returns true if there is a saved run that can be continued.
+pub fn has_saved_run() -> bool {
+    save_file_path().is_ok_and(|path| path.exists())
+}
+
+/// Writes the run to the save file, replacing any previously saved run.
+pub fn save_run(app: &App) -> color_eyre::Result<()> {
+    let contents = serde_json::to_string(&SavedRun::from(app))?;
+    fs::write(save_file_path()?, contents)?;
+
+    Ok(())
+}
+
+/// Reads the saved run, if there is one.
+pub fn load_run() -> color_eyre::Result<Option<SavedRun>> {
+    match fs::read_to_string(save_file_path()?) {
+        Ok(contents) => Ok(Some(parse_saved_run(&contents)?)),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error.into()),
+    }
+}
+
+/// Deletes the saved run, if there is one.
+pub fn delete_saved_run() -> color_eyre::Result<()> {
+    match fs::remove_file(save_file_path()?) {
+        Err(error) if error.kind() != ErrorKind::NotFound => Err(error.into()),
+        _ => Ok(()),
+    }
+}
+
+fn parse_saved_run(contents: &str) -> color_eyre::Result<SavedRun> {
+    let value: serde_json::Value = serde_json::from_str(contents)?;
+    let version = value
+        .get("version")
+        .and_then(|version| version.as_u64())
+        .ok_or_eyre("Save file has no version")?;
+
+    match version {
+        1 => Ok(serde_json::from_value(value)?),
+        _ => bail!("Save file version {version} is not supported by this version of Scoundrel"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::{App, CurrentScreen, Settings};
+    use crate::engine::action::Action;
+    use crate::storage::save::{SavedRun, parse_saved_run};
+
+    #[test]
+    fn saved_run_should_resume_where_it_was_left() {
+        let mut app = App::new(Settings::default(), Some(42));
+        app.start_new_run();
+        app.apply(Action::AvoidRoom).unwrap();
+        app.apply(Action::PickCard(1)).unwrap();
+
+        let contents = serde_json::to_string(&SavedRun::from(&app)).unwrap();
+        let mut resumed_app = App::default();
+        resumed_app.resume_run(parse_saved_run(&contents).unwrap());
+
+        assert!(resumed_app.current_screen == app.current_screen);
+        assert_eq!(resumed_app.engine.life(), app.engine.life());
+        assert_eq!(
+            resumed_app.engine.hand().to_string(),
+            app.engine.hand().to_string()
+        );
+        assert!(resumed_app.engine.deck().iter().eq(app.engine.deck().iter()));
+    }
+
+    #[test]
+    fn run_saved_from_the_leave_run_dialog_should_resume_in_the_room() {
+        let mut app = App::new(Settings::default(), Some(42));
+        app.start_new_run();
+        app.apply(Action::EnterRoom).unwrap();
+        app.current_screen = CurrentScreen::ExitingRound;
+
+        let contents = serde_json::to_string(&SavedRun::from(&app)).unwrap();
+        let mut resumed_app = App::default();
+        resumed_app.resume_run(parse_saved_run(&contents).unwrap());
+
+        assert!(resumed_app.current_screen == CurrentScreen::ChooseCard);
+    }
+
+    #[test]
+    fn only_the_resumed_run_should_be_the_saved_run() {
+        let mut app = App::new(Settings::default(), Some(42));
+        app.start_new_run();
+        let contents = serde_json::to_string(&SavedRun::from(&app)).unwrap();
+
+        app.resume_run(parse_saved_run(&contents).unwrap());
+        assert!(app.is_saved_run);
+
+        // finishing or abandoning a new run must not delete the save of the other run
+        app.start_new_run();
+        assert!(!app.is_saved_run);
+    }
+
+    #[test]
+    fn saves_from_unknown_versions_should_be_rejected() {
+        assert!(parse_saved_run(r#"{"version": 999}"#).is_err());
+        assert!(parse_saved_run(r#"{"life": 20}"#).is_err());
+    }
+}
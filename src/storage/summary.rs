@@ -0,0 +1,135 @@
+//! Text summaries of runs that can be shared, kept in the `summaries` directory.
+//!
+//! A summary starts with the seed and the score, followed by one line of emojis per room:
+//!
+//! ```text
+//! Scoundrel seed 7 (Official (44 cards)): Died with a score of -9 (3 rooms cleared)
+//! 🏃
+//! ❤️⚔️👺🛡️
+//! 👺👊❤️💨👺🛡️
+//! 👺👊💀
+//! ```
+//!
+//! Each card that was played shows its emoji - 👺 for monsters, ⚔️ for weapons and ❤️ for potions -
+//! followed by how it went: 🛡️ for a monster blocked with the weapon, 👊 for one fought bare-knuckle,
+//! and 💨 for a potion that had no effect. 🏃 marks a room that was fled, and the run ends with 🏆 or 💀.
+use crate::cards::emoji::Emoji;
+use crate::engine::engine::{Engine, Phase};
+use crate::engine::event::Event;
+use crate::engine::replay::Replay;
+use crate::engine::rule_error::RuleError;
+use crate::storage::daily::share_result;
+use crate::storage::data_dir;
+use chrono::{DateTime, Local, NaiveDate};
+use std::fs;
+use std::path::PathBuf;
+
+const SUMMARIES_DIR_NAME: &str = "summaries";
+
+const BLOCKED: &str = "🛡️";
+const BARE_KNUCKLE: &str = "👊";
+const WASTED: &str = "💨";
+const FLED: &str = "🏃";
+const WON: &str = "🏆";
+const DIED: &str = "💀";
+
+/// Describes how the run ended, e.g. "Survived with a score of 12 (13 rooms cleared)".
+pub fn describe_outcome(engine: &Engine) -> String {
+    let outcome = if engine.phase() == Phase::Won {
+        "Survived"
+    } else {
+        "Died"
+    };
+
+    format!(
+        "{outcome} with a score of {} ({} rooms cleared)",
+        engine.calculate_score(),
+        engine.stats().rooms_cleared
+    )
+}
+
+/// Summarizes the run of the replay - daily challenge runs are titled with their date instead of the seed.
+pub fn run_summary(replay: &Replay, daily: Option<NaiveDate>) -> Result<String, RuleError> {
+    let mut engine = Engine::new(replay.variant, replay.seed);
+    let mut rooms = vec![String::new()];
+
+    for action in &replay.actions {
+        for event in engine.apply(*action)? {
+            let marker = match event {
+                Event::Healed { potion, .. } => potion.get_emoji(),
+                Event::PotionWasted { potion } => format!("{}{WASTED}", potion.get_emoji()),
+                Event::WeaponEquipped { weapon } => weapon.get_emoji(),
+                Event::CreatureBlocked { creature, .. } => {
+                    format!("{}{BLOCKED}", creature.get_emoji())
+                }
+                Event::CreatureFoughtBareKnuckle { creature, .. } => {
+                    format!("{}{BARE_KNUCKLE}", creature.get_emoji())
+                }
+                Event::RoomAvoided => FLED.to_string(),
+                Event::GameWon { .. } => WON.to_string(),
+                Event::GameLost { .. } => DIED.to_string(),
+                Event::RoomDealt => {
+                    rooms.push(String::new());
+                    continue;
+                }
+                Event::RoomEntered | Event::CombatStarted { .. } => continue,
+            };
+            if let Some(room) = rooms.last_mut() {
+                room.push_str(&marker);
+            }
+        }
+    }
+
+    let title = match daily {
+        Some(date) => share_result(date, &engine),
+        None => format!(
+            "Scoundrel seed {} ({}): {}",
+            replay.seed,
+            replay.variant,
+            describe_outcome(&engine)
+        ),
+    };
+    let mut lines = vec![title];
+    lines.extend(rooms.into_iter().filter(|room| !room.is_empty()));
+
+    Ok(lines.join("\n"))
+}
+
+/// Writes the summary of a run that finished at `finished_at`, and returns the path of the file.
+pub fn save_summary(
+    summary: &str,
+    replay: &Replay,
+    finished_at: DateTime<Local>,
+) -> color_eyre::Result<PathBuf> {
+    let dir = data_dir()?.join(SUMMARIES_DIR_NAME);
+    fs::create_dir_all(&dir)?;
+
+    let path = dir.join(format!(
+        "{}-{}.txt",
+        finished_at.format("%Y%m%d-%H%M%S"),
+        replay.seed
+    ));
+    fs::write(&path, summary)?;
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::bot::greedy::GreedyBot;
+    use crate::bot::simulate::play_game;
+    use crate::cards::deck::DeckVariant;
+    use crate::storage::summary::run_summary;
+
+    #[test]
+    fn summary_should_have_one_line_per_room_played() {
+        let engine = play_game(&mut GreedyBot, DeckVariant::Official, 42).unwrap();
+        let summary = run_summary(&engine.replay(), None).unwrap();
+        let lines: Vec<&str> = summary.lines().collect();
+
+        assert!(lines[0].starts_with("Scoundrel seed 42"));
+        assert!(lines.last().unwrap().ends_with('🏆') || lines.last().unwrap().ends_with('💀'));
+        // rooms that were cleared, plus fled rooms, plus the room the run ended in (if it was not cleared)
+        assert!(lines.len() > engine.stats().rooms_cleared as usize);
+    }
+}
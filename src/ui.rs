@@ -1,241 +1,1108 @@
-use crate::app::{App, CurrentScreen, MAX_LIFE};
-use crate::cards::deck::{Card, MAX_DECK_SIZE, Suite};
-use color_eyre::owo_colors::OwoColorize;
+use crate::app::{App, CurrentScreen, MenuItem, SettingsItem};
+use crate::bot::hint::Hint;
+use crate::bot::risk::{RiskForecast, RoomOutcome};
+use crate::engine::action::Action;
+use crate::engine::discard::CardStatus;
+use crate::engine::engine::{Engine, MAX_LIFE, Phase};
+use crate::engine::rules::describe_rules;
+use crate::game::tutorial::Tutorial;
+use crate::cards::deck::{Card, Rank, Suite};
+use crate::cards::emoji::Emoji;
+use crate::storage::daily::share_result;
+use crate::storage::history::{RunRecord, RunStatistics, daily_leaderboard};
+use clap::ValueEnum;
+use strum::IntoEnumIterator;
 use ratatui::Frame;
 use ratatui::layout::Constraint::Percentage;
-use ratatui::prelude::Color::Gray;
-use ratatui::prelude::Constraint::Fill;
+use ratatui::prelude::Constraint::{Fill, Length};
 use ratatui::prelude::{Color, Direction, Layout, Line, Rect, Span};
-use ratatui::style::Style;
+use ratatui::style::{Modifier, Style};
 use ratatui::style::palette::tailwind;
 use ratatui::text::Text;
-use ratatui::widgets::{Block, Borders, Gauge, List, ListItem, Paragraph};
-use std::io::BufRead;
+use ratatui::widgets::{Block, Borders, Cell, Clear, Gauge, List, ListItem, Paragraph, Row, Table, Wrap};
 
-trait ListStyle {
-    fn get_list_style(&self) -> Style;
+const FORECAST_BUCKET_SIZE: i32 = 5; // life lost is grouped in ranges of this many points
+const FORECAST_BAR_WIDTH: usize = 40; // length of the bar of a certain outcome
+const CARDS_SEEN_WIDTH: u16 = 46; // the grid of cards seen, and the discard pile below it
+pub const NO_RUNS_YET: &str = "No runs finished yet - go play one!";
+const MAX_LEADERBOARD_ROWS: usize = 5; // the daily leaderboard shows the best runs only
+const TUTORIAL_EXPLANATION_HEIGHT: u16 = 6;
+// the game is over, so there is no move left to undo
+const END_OF_GAME_INSTRUCTIONS: &str = "Press (c) to copy a summary of your run, (w) to write it to a file, or (enter) to return to the menu.";
+
+/// The colours of the interface.
+#[derive(Clone, Copy, Default, PartialEq, Eq, ValueEnum, Debug)]
+pub enum Theme {
+    /// Light text, for terminals with a dark background
+    #[default]
+    Dark,
+    /// Dark text on a light background
+    Light,
 }
 
-trait Emoji {
-    fn get_emoji(&self) -> String;
+impl Theme {
+    /// Style of the screen that everything is drawn on.
+    fn base(&self) -> Style {
+        match self {
+            Theme::Dark => Style::default().fg(Color::White),
+            Theme::Light => Style::default().fg(Color::Black).bg(Color::White),
+        }
+    }
+
+    /// Style of popups, which stand out from the screen behind them.
+    fn dialog(&self) -> Style {
+        match self {
+            Theme::Dark => Style::default().fg(Color::White).bg(Color::Black),
+            Theme::Light => Style::default().fg(Color::Black).bg(Color::Gray),
+        }
+    }
+
+    /// Style of the screen while the player decides whether to enter the room.
+    fn before_room(&self) -> Style {
+        match self {
+            Theme::Dark => Style::default().bg(Color::DarkGray),
+            Theme::Light => Style::default().bg(Color::Gray),
+        }
+    }
+
+    /// Style of a card in the room, by the kind of card that its suit makes it.
+    fn card(&self, suite: Suite) -> Style {
+        let text = match self {
+            Theme::Dark => Color::White,
+            Theme::Light => Color::Black,
+        };
+        let background = match (self, suite) {
+            (Theme::Dark, Suite::Spade | Suite::Club) => Color::Red,
+            (Theme::Dark, Suite::Diamond) => Color::Magenta,
+            (Theme::Dark, Suite::Heart) => Color::Green,
+            (Theme::Light, Suite::Spade | Suite::Club) => Color::LightRed,
+            (Theme::Light, Suite::Diamond) => Color::LightMagenta,
+            (Theme::Light, Suite::Heart) => Color::LightGreen,
+        };
+        Style::default().fg(text).bg(background)
+    }
+
+    /// Style of the slot of a card that was used.
+    fn used_card(&self) -> Style {
+        match self {
+            Theme::Dark => Style::default().fg(Color::Black).bg(Color::Gray),
+            Theme::Light => Style::default().fg(Color::White).bg(Color::DarkGray),
+        }
+    }
+
+    /// Style of the selected item of a menu.
+    fn selected(&self) -> Style {
+        match self {
+            Theme::Dark => Style::default().fg(Color::Black).bg(Color::Magenta),
+            Theme::Light => Style::default().fg(Color::White).bg(Color::Magenta),
+        }
+    }
+
+    /// Style of things that cannot be used any more, e.g. a used card.
+    fn disabled(&self) -> Style {
+        match self {
+            Theme::Dark => Style::default().fg(Color::DarkGray),
+            Theme::Light => Style::default().fg(Color::Gray),
+        }
+    }
+
+    /// Style of the details of the game that change as it is played, e.g. the equipped weapon -
+    /// blue can be read on either background.
+    fn game_info(&self) -> Style {
+        Style::default().fg(Color::Blue)
+    }
+
+    /// Style of the messages that tell the player what happened.
+    fn notification(&self) -> Style {
+        match self {
+            Theme::Dark => Style::default().fg(Color::LightBlue),
+            Theme::Light => Style::default().fg(Color::Blue),
+        }
+    }
+
+    /// Style of the outcome of a run - green can be read on either background.
+    fn outcome(&self) -> Style {
+        Style::default().fg(Color::Green)
+    }
+
+    /// Style of the numbers written over a gauge.
+    fn gauge_label(&self) -> Style {
+        match self {
+            Theme::Dark => Style::default().fg(Color::White),
+            Theme::Light => Style::default().fg(Color::Black),
+        }
+    }
+
+    /// Style of the best runs in the past runs.
+    fn best_run(&self) -> Style {
+        match self {
+            Theme::Dark => Style::default().fg(Color::Yellow),
+            // yellow cannot be read on a light background
+            Theme::Light => Style::default().fg(Color::Indexed(136)),
+        }
+    }
 }
 
 pub fn ui(frame: &mut Frame, app: &App) {
+    // text that is not given a colour of its own takes the colour of the theme
+    frame.render_widget(Block::default().style(app.theme.base()), frame.area());
+
+    if let Some(tutorial) = &app.tutorial
+        && matches!(
+            app.current_screen,
+            CurrentScreen::BeforeRoom
+                | CurrentScreen::ChooseCard
+                | CurrentScreen::ChooseWeaponOrBareKnuckle
+                | CurrentScreen::Won
+                | CurrentScreen::Lost
+        )
+    {
+        render_tutorial(frame, app, tutorial);
+        return;
+    }
+
     match app.current_screen {
-        CurrentScreen::Welcome => {
-            let welcome_message = Paragraph::new(Text::from(vec![
-                Line::styled(
-                    "Welcome to Scoundrel, a single-player game that you can play with any deck of cards.",
-                    Style::default().fg(Color::White),
-                ).centered(),
-
-                Line::styled(
-                    "Press any button to continue; or press (q) to quit.",
-                    Style::default().fg(Color::Magenta),
-                ).centered(),
-
-                Line::styled(
-                    "How to play (links to YouTube - credits to @Rulies): https://www.youtube.com/watch?v=Gt2tYzM93h4",
-                    Style::default().fg(Color::White),
-                ).centered(),
-            ]));
-
-            frame.render_widget(welcome_message, centered_rect(80, 30, frame.area()));
+        CurrentScreen::Menu => render_menu(frame, app),
+
+        CurrentScreen::Exiting => {
+            render_menu(frame, app);
+            render_dialog(
+                frame,
+                app.theme,
+                "Quit",
+                vec![Line::from("Are you sure you want to quit? (y/n)").centered()],
+            );
+        }
+
+        CurrentScreen::ExitingRound => {
+            render_dialog(
+                frame,
+                app.theme,
+                "Leave run",
+                vec![
+                    Line::from("Do you want to leave this run?").centered(),
+                    Line::from("").centered(),
+                    Line::from("(s) save it and continue later").centered(),
+                    Line::from("(a) abandon it").centered(),
+                    Line::from("(esc) keep playing").centered(),
+                ],
+            );
         }
 
         CurrentScreen::BeforeRoom => {
+            // a room cannot be avoided twice in a row
+            let mut title = if app.engine.legal_actions().contains(&Action::AvoidRoom) {
+                "Enter room? (y/n)".to_string()
+            } else {
+                "Enter room? (y)".to_string()
+            };
+            if app.daily.is_none() {
+                title.push_str(if app.forecast.is_some() {
+                    " - hide forecast (f)"
+                } else {
+                    " - forecast (f)"
+                });
+            }
+            if let Some(hint) = &app.hint {
+                let outcomes = hint
+                    .outcomes
+                    .iter()
+                    .map(|(action, outcome)| format!("{action}: {outcome}"))
+                    .collect::<Vec<_>>();
+                title.push_str(&format!(
+                    " - hint: {} ({})",
+                    hint.recommended,
+                    outcomes.join(", ")
+                ));
+            }
             let block = Block::default()
-                .title("Enter room? (y/n)")
-                .style(Style::default().bg(Color::Gray));
+                .title(title)
+                .style(app.theme.before_room());
             frame.render_widget(block, frame.area());
 
-            let layout = Layout::default()
-                .direction(Direction::Vertical)
-                .constraints(vec![
-                    Percentage(10),
-                    Percentage(50),
-                    Percentage(30),
-                    Percentage(10),
-                ])
-                .margin(3)
-                .split(frame.area());
-
-            let number_of_cards_left_area = layout[0];
-            render_number_of_cards_left(frame, app, number_of_cards_left_area);
-
-            // display cards in hand
-            let cards_in_hand_area = layout[1];
-            render_cards(frame, app, cards_in_hand_area);
-
-            // to display life points, number of cards left, etc
-            let stats_and_weapons_area = Layout::default()
-                .direction(Direction::Horizontal)
-                .constraints(vec![Fill(1), Fill(1)])
-                .split(layout[2]);
-            let stats_area = stats_and_weapons_area[0];
-            let equipped_weapon_area = stats_and_weapons_area[1];
-            render_health(frame, app, stats_area);
-            render_equipped_weapon(frame, app, equipped_weapon_area);
-
-            // to display messages (e.g. lost 4 health points, equipped weapon, etc)
-            let notifications_area = layout[3];
-            render_notifications(frame, app, notifications_area);
+            render_dungeon(
+                frame,
+                app.theme,
+                &app.engine,
+                frame.area(),
+                RoomPanel {
+                    title: "Room - select card (1/2/3/4)",
+                    controls: Some(&controls(app)),
+                    hint: app.hint.as_ref(),
+                },
+                app.notifications.last().map(String::as_str),
+            );
+            if let Some(forecast) = &app.forecast {
+                render_forecast(frame, app.theme, forecast);
+            }
         }
 
-        CurrentScreen::ChooseCard => {
-            let layout = Layout::default()
-                .direction(Direction::Vertical)
-                .constraints(vec![
-                    Percentage(10),
-                    Percentage(50),
-                    Percentage(30),
-                    Percentage(10),
-                ])
-                .margin(3)
-                .split(frame.area());
-
-            let number_of_cards_left_area = layout[0];
-            render_number_of_cards_left(frame, app, number_of_cards_left_area);
-
-            // display cards in hand
-            let cards_in_hand_area = layout[1];
-            render_cards(frame, app, cards_in_hand_area);
-
-            // to display life points, number of cards left, etc
-            let stats_and_weapons_area = Layout::default()
-                .direction(Direction::Horizontal)
-                .constraints(vec![Fill(1), Fill(1)])
-                .split(layout[2]);
-            let stats_area = stats_and_weapons_area[0];
-            let equipped_weapon_area = stats_and_weapons_area[1];
-            render_health(frame, app, stats_area);
-            render_equipped_weapon(frame, app, equipped_weapon_area);
-
-            // to display messages (e.g. lost 4 health points, equipped weapon, etc)
-            let notifications_area = layout[3];
-            render_notifications(frame, app, notifications_area);
+        CurrentScreen::ChooseCard => render_dungeon(
+            frame,
+            app.theme,
+            &app.engine,
+            frame.area(),
+            RoomPanel {
+                title: "Room - select card (1/2/3/4)",
+                controls: Some(&controls(app)),
+                hint: app.hint.as_ref(),
+            },
+            app.notifications.last().map(String::as_str),
+        ),
+
+        CurrentScreen::ChooseWeaponOrBareKnuckle => render_combat(frame, app.theme, &app.engine),
+
+        CurrentScreen::Won | CurrentScreen::Lost => render_end_of_run(frame, app),
+
+        CurrentScreen::PastRuns => render_past_runs(frame, app, frame.area()),
+
+        CurrentScreen::Statistics => render_statistics(frame, app, frame.area()),
+
+        CurrentScreen::Settings => render_settings(frame, app, frame.area()),
+
+        CurrentScreen::Replay => render_replay(frame, app),
+
+        CurrentScreen::Rules => render_rules(frame, app, frame.area()),
+    }
+}
+
+/// What the list of the cards in the room shows besides the cards.
+struct RoomPanel<'a> {
+    title: &'a str,
+    controls: Option<&'a str>,
+    hint: Option<&'a Hint>,
+}
+
+impl RoomPanel<'_> {
+    /// A room that is only watched, e.g. in a replay.
+    const WATCHED: RoomPanel<'static> = RoomPanel {
+        title: "Room",
+        controls: None,
+        hint: None,
+    };
+}
+
+/// Renders the progress, room, health and weapon of a game - shared by the run and replay screens.
+fn render_dungeon(
+    frame: &mut Frame,
+    theme: Theme,
+    engine: &Engine,
+    area: Rect,
+    room_panel: RoomPanel,
+    message: Option<&str>,
+) {
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![
+            Percentage(10),
+            Percentage(50),
+            Percentage(30),
+            Percentage(10),
+        ])
+        .margin(3)
+        .split(area);
+
+    let number_of_cards_left_area = layout[0];
+    render_number_of_cards_left(frame, engine, number_of_cards_left_area);
+
+    // display cards in hand, next to the cards that were seen so far
+    let room_and_seen_cards_area = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![Fill(1), Length(CARDS_SEEN_WIDTH)])
+        .split(layout[1]);
+    let cards_in_hand_area = room_and_seen_cards_area[0];
+    render_cards(frame, theme, engine, room_panel, cards_in_hand_area);
+    render_cards_seen(frame, theme, engine, room_and_seen_cards_area[1]);
+
+    // to display life points, number of cards left, etc
+    let stats_and_weapons_area = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![Fill(1), Fill(1)])
+        .split(layout[2]);
+    let stats_area = stats_and_weapons_area[0];
+    let equipped_weapon_area = stats_and_weapons_area[1];
+    render_health(frame, theme, engine, stats_area);
+    render_equipped_weapon(frame, theme, engine, equipped_weapon_area);
+
+    // to display messages (e.g. lost 4 health points, equipped weapon, etc)
+    let notifications_area = layout[3];
+    render_notifications(frame, theme, message, notifications_area);
+}
+
+/// Renders the odds of the room that was dealt, and of the room that would be dealt after fleeing from it.
+fn render_forecast(frame: &mut Frame, theme: Theme, forecast: &RiskForecast) {
+    let this_room = match forecast.this_room {
+        RoomOutcome::Dies => "there is no way to survive this room".to_string(),
+        RoomOutcome::LosesLife(life_lost) if life_lost < 0 => {
+            format!("the best way to play this room gains {} life", -life_lost)
         }
+        RoomOutcome::LosesLife(life_lost) => {
+            format!("the best way to play this room loses {life_lost} life")
+        }
+    };
+    let bar = |chance: f64| "█".repeat((chance * FORECAST_BAR_WIDTH as f64).round() as usize);
 
-        CurrentScreen::ChooseWeaponOrBareKnuckle => {
-            let popup_area = centered_rect(70, 50, frame.area());
+    let mut lines = vec![
+        Line::from(format!("If you enter: {this_room}")),
+        Line::from(""),
+        Line::from("If you flee, the next room is dealt from the cards you have not seen:"),
+        Line::from(format!(
+            "  chance of death: {:.1}% (out of {} rooms dealt at random)",
+            forecast.death_chance() * 100.0,
+            forecast.samples
+        )),
+        Line::from(format!(
+            "  life lost if you survive, with the best play: {:.1} on average",
+            forecast.average_life_lost()
+        )),
+        Line::from(""),
+    ];
+    for (life_lost, chance) in forecast.life_lost_distribution(FORECAST_BUCKET_SIZE) {
+        lines.push(Line::from(format!(
+            "  {:>3} to {:>3} life  {:>5.1}% {}",
+            life_lost,
+            life_lost + FORECAST_BUCKET_SIZE - 1,
+            chance * 100.0,
+            bar(chance)
+        )));
+    }
+    if forecast.deaths > 0 {
+        lines.push(Line::from(format!(
+            "  death           {:>5.1}% {}",
+            forecast.death_chance() * 100.0,
+            bar(forecast.death_chance())
+        )));
+    }
 
-            let block = Block::default().title("Use equipped weapon? (y/n)");
+    let area = centered_rect(70, 60, frame.area());
+    let popup = Paragraph::new(Text::from(lines)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(Line::from("Forecast").centered())
+            .title_bottom(Line::from("hide (f)").centered())
+            .style(theme.dialog()),
+    );
+    frame.render_widget(Clear, area);
+    frame.render_widget(popup, area);
+}
 
-            frame.render_widget(block, popup_area);
+/// Renders the choice between fighting the creature with the equipped weapon or bare-knuckle.
+fn render_combat(frame: &mut Frame, theme: Theme, engine: &Engine) {
+    let popup_area = centered_rect(70, 50, frame.area());
 
-            let chunks = Layout::default()
-                .direction(Direction::Horizontal)
-                .constraints(vec![Percentage(50), Percentage(50)])
-                .margin(2)
-                .split(popup_area);
+    let block = Block::default().title("Use equipped weapon? (y/n)");
 
-            let weapon = Paragraph::new(Text::styled(
-                format!(
-                    "{} {}",
-                    app.equipped_weapon.as_ref().unwrap(),
-                    app.equipped_weapon.as_ref().unwrap().get_emoji()
-                ),
-                Style::default().fg(Color::Blue),
-            ))
-            .centered()
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title_bottom("Your equipped weapon"),
-            );
-            let creature = Paragraph::new(Text::styled(
-                format!(
-                    "{} {}",
-                    app.in_combat_with_creature.as_ref().unwrap(),
-                    app.in_combat_with_creature.as_ref().unwrap().get_emoji()
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![Percentage(50), Percentage(50)])
+        .margin(2)
+        .split(popup_area);
+
+    let (Some(weapon), Some(creature)) = (engine.equipped_weapon(), engine.in_combat_with_creature())
+    else {
+        return;
+    };
+
+    let weapon = Paragraph::new(Text::styled(
+        format!("{} {}", weapon, weapon.get_emoji()),
+        theme.game_info(),
+    ))
+    .centered()
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title_bottom("Your equipped weapon"),
+    );
+    let creature = Paragraph::new(Text::styled(
+        format!("{} {}", creature, creature.get_emoji()),
+        theme.game_info(),
+    ))
+    .centered()
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title_bottom("Creature"),
+    );
+
+    frame.render_widget(weapon, chunks[0]);
+    frame.render_widget(creature, chunks[1]);
+}
+
+fn render_replay(frame: &mut Frame, app: &App) {
+    let Some(engine) = app.replay_state() else {
+        return;
+    };
+
+    let block = Block::default()
+        .title(format!(
+            "Replay - step {} of {}",
+            app.replay_step,
+            app.replay_actions.len()
+        ))
+        .title_bottom("previous (left), next (right), start (home), end (end), back (esc)");
+    frame.render_widget(block, frame.area());
+
+    let message = match app.replay_actions.get(app.replay_step) {
+        Some(action) => format!("Next move: {action}"),
+        None => format!(
+            "End of the run - {} with a score of {}",
+            if engine.phase() == Phase::Won {
+                "survived"
+            } else {
+                "died"
+            },
+            engine.calculate_score()
+        ),
+    };
+    render_dungeon(
+        frame,
+        app.theme,
+        engine,
+        frame.area(),
+        RoomPanel::WATCHED,
+        Some(&message),
+    );
+
+    if engine.phase() == Phase::ChooseWeaponOrBareKnuckle {
+        render_combat(frame, app.theme, engine);
+    }
+}
+
+/// Renders the run of the tutorial, with the explanation of the current step below it.
+fn render_tutorial(frame: &mut Frame, app: &App, tutorial: &Tutorial) {
+    let Some(step) = tutorial.current_step() else {
+        return;
+    };
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![Fill(1), Length(TUTORIAL_EXPLANATION_HEIGHT)])
+        .split(frame.area());
+
+    let (step_num, num_steps) = tutorial.progress();
+    let block = Block::default()
+        .title(format!("Tutorial - step {step_num} of {num_steps}"))
+        .title_bottom("leave the tutorial (q)");
+    frame.render_widget(block, layout[0]);
+    render_dungeon(
+        frame,
+        app.theme,
+        &app.engine,
+        layout[0],
+        RoomPanel::WATCHED,
+        app.notifications.last().map(String::as_str),
+    );
+    if app.engine.phase() == Phase::ChooseWeaponOrBareKnuckle {
+        render_combat(frame, app.theme, &app.engine);
+    }
+
+    let expected_key = match tutorial.expected_action(&app.engine) {
+        Some(action) => format!("press ({})", key_for_action(action)),
+        None => "continue (enter)".to_string(),
+    };
+    let explanation = Paragraph::new(step.explanation)
+        .wrap(Wrap { trim: true })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("How to play")
+                .title_bottom(Line::from(expected_key).centered())
+                .style(app.theme.dialog()),
+        );
+    frame.render_widget(explanation, layout[1]);
+}
+
+/// The key to press for an action during a run.
+fn key_for_action(action: Action) -> String {
+    match action {
+        Action::EnterRoom | Action::FightWithWeapon => "y".to_string(),
+        Action::AvoidRoom | Action::FightBareKnuckle => "n".to_string(),
+        Action::PickCard(card_num) => card_num.to_string(),
+    }
+}
+
+fn render_menu(frame: &mut Frame, app: &App) {
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![Percentage(30), Percentage(60), Percentage(10)])
+        .margin(3)
+        .split(frame.area());
+
+    let welcome_message = Paragraph::new(Text::from(vec![
+        Line::styled(
+            "Welcome to Scoundrel, a single-player game that you can play with any deck of cards.",
+            Style::default(),
+        )
+        .centered(),
+        Line::styled(
+            "How to play (links to YouTube - credits to @Rulies): https://www.youtube.com/watch?v=Gt2tYzM93h4",
+            Style::default(),
+        )
+        .centered(),
+        Line::styled(
+            "New to the game? Learn the rules as you play with the Tutorial.",
+            Style::default(),
+        )
+        .centered(),
+    ]));
+    frame.render_widget(welcome_message, layout[0]);
+
+    let menu_items: Vec<ListItem> = MenuItem::iter()
+        .map(|item| {
+            let style = if item == app.menu_selection {
+                app.theme.selected()
+            } else if item == MenuItem::Continue && !app.has_saved_run {
+                app.theme.disabled()
+            } else {
+                Style::default()
+            };
+            ListItem::new(Line::styled(item.to_string(), style).centered())
+        })
+        .collect();
+    let menu = List::new(menu_items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(Line::from("Menu").centered())
+            .title_bottom(Line::from("select (up/down), confirm (enter)").centered()),
+    );
+    frame.render_widget(menu, centered_rect(40, 80, layout[1]));
+
+    render_notifications(frame, app.theme, app.notifications.last().map(String::as_str), layout[2]);
+}
+
+/// Renders a popup in the middle of the screen.
+fn render_dialog(frame: &mut Frame, theme: Theme, title: &str, lines: Vec<Line>) {
+    let area = centered_rect(50, 30, frame.area());
+    let dialog = Paragraph::new(Text::from(lines)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(Line::from(title.to_string()).centered())
+            .style(theme.dialog()),
+    );
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(dialog, area);
+}
+
+fn render_statistics(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Statistics")
+        .title_bottom("back (esc)");
+
+    let text = match RunStatistics::from_runs(&app.past_runs) {
+        Some(statistics) => statistics.to_string(),
+        None => NO_RUNS_YET.to_string(),
+    };
+
+    let statistics = Paragraph::new(Text::from(text)).block(block);
+    frame.render_widget(statistics, area);
+}
+
+fn render_settings(frame: &mut Frame, app: &App, area: Rect) {
+    let items: Vec<ListItem> = SettingsItem::iter()
+        .map(|item| {
+            let label = match item {
+                SettingsItem::Variant => format!("Deck: {}", app.settings.variant),
+                SettingsItem::AllowUndo => format!(
+                    "Undo: {}",
+                    if app.settings.allow_undo {
+                        "allowed"
+                    } else {
+                        "not allowed (ironman)"
+                    }
                 ),
-                Style::default().fg(Color::Blue),
-            ))
-            .centered()
-            .block(
+            };
+            let style = if item == app.settings_selection {
+                app.theme.selected()
+            } else {
+                Style::default()
+            };
+            ListItem::new(Line::styled(label, style))
+        })
+        .collect();
+
+    let settings = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Settings - applied to the next new run")
+            .title_bottom("select (up/down), change (enter), back (esc)"),
+    );
+    frame.render_widget(settings, area);
+}
+
+fn render_rules(frame: &mut Frame, app: &App, area: Rect) {
+    let sections = describe_rules(app.rules_variant);
+    let num_sections = sections.len();
+
+    let mut lines = Vec::new();
+    for section in sections.into_iter().skip(app.rules_scroll) {
+        lines.push(Line::styled(
+            section.title,
+            Style::default().add_modifier(Modifier::BOLD),
+        ));
+        lines.extend(section.paragraphs.into_iter().map(Line::from));
+        lines.push(Line::from(""));
+    }
+
+    let rules = Paragraph::new(Text::from(lines))
+        .wrap(Wrap { trim: true })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(
+                    "Rules - {:?} deck (section {} of {})",
+                    app.rules_variant,
+                    app.rules_scroll + 1,
+                    num_sections
+                ))
+                .title_bottom("scroll (up/down), back (esc)"),
+        );
+    frame.render_widget(rules, area);
+}
+
+fn render_past_runs(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(
+            "Past runs - sorted by {} (s)",
+            app.past_runs_sort
+        ))
+        .title_bottom("scroll (up/down), watch replay of the top run (enter), back (esc)");
+
+    if app.past_runs.is_empty() {
+        let message = Paragraph::new(NO_RUNS_YET).block(block);
+        frame.render_widget(message, area);
+        return;
+    }
+
+    let best_score = app.past_runs.iter().map(|run| run.score).max();
+
+    let header = Row::new(vec![
+        "Date", "Seed", "Deck", "Result", "Score", "Rooms", "Turns", "Damage", "Potions",
+    ])
+    .style(Style::default().add_modifier(Modifier::BOLD));
+
+    let rows = app.past_runs.iter().skip(app.past_runs_scroll).enumerate().map(|(idx, run)| {
+        // highlight the best run(s)
+        let style = if Some(run.score) == best_score {
+            app.theme.best_run().add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        // the run at the top is the one whose replay is shown
+        let style = if idx == 0 {
+            style.add_modifier(Modifier::REVERSED)
+        } else {
+            style
+        };
+        let result = match (run.won, run.used_undo) {
+            (true, false) => "Won",
+            (true, true) => "Won (undo used)",
+            (false, false) => "Lost",
+            (false, true) => "Lost (undo used)",
+        };
+
+        Row::new(vec![
+            run.finished_at.format("%Y-%m-%d %H:%M").to_string(),
+            run.seed.to_string(),
+            run.variant.to_string(),
+            result.to_string(),
+            run.score.to_string(),
+            run.rooms_cleared.to_string(),
+            run.turns.to_string(),
+            run.damage_taken.to_string(),
+            run.potions_used.to_string(),
+        ])
+        .style(style)
+    });
+
+    let table = Table::new(
+        rows,
+        [
+            Length(16),
+            Length(20),
+            Length(18),
+            Length(16),
+            Length(6),
+            Length(6),
+            Length(6),
+            Length(7),
+            Length(8),
+        ],
+    )
+    .header(header)
+    .block(block);
+
+    let daily_runs = daily_leaderboard(&app.past_runs);
+    // the leaderboard is only shown once a daily challenge was played
+    let leaderboard_height = if daily_runs.is_empty() {
+        0
+    } else {
+        daily_runs.len().min(MAX_LEADERBOARD_ROWS) as u16 + 3
+    };
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![Length(leaderboard_height), Fill(1), Length(1)])
+        .split(area);
+    render_daily_leaderboard(frame, &daily_runs, layout[0]);
+    frame.render_widget(table, layout[1]);
+    render_notifications(frame, app.theme, app.notifications.last().map(String::as_str), layout[2]);
+}
+
+fn render_daily_leaderboard(frame: &mut Frame, daily_runs: &[&RunRecord], area: Rect) {
+    let header = Row::new(vec!["Rank", "Daily", "Result", "Score", "Rooms"])
+        .style(Style::default().add_modifier(Modifier::BOLD));
+    let rows = daily_runs.iter().take(MAX_LEADERBOARD_ROWS).enumerate().map(|(idx, run)| {
+        Row::new(vec![
+            format!("#{}", idx + 1),
+            run.daily.map(|date| date.to_string()).unwrap_or_default(),
+            if run.won { "Won" } else { "Lost" }.to_string(),
+            run.score.to_string(),
+            run.rooms_cleared.to_string(),
+        ])
+    });
+
+    let table = Table::new(rows, [Length(5), Length(11), Length(7), Length(6), Length(6)])
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Daily challenge leaderboard - best scores"),
+        );
+    frame.render_widget(table, area);
+}
+
+fn render_end_of_run(frame: &mut Frame, app: &App) {
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![Length(8), Length(8), Fill(1), Length(1)])
+        .margin(1)
+        .split(frame.area());
+    render_notifications(frame, app.theme, app.notifications.last().map(String::as_str), layout[3]);
+
+    let outcome = if app.engine.phase() == Phase::Won {
+        "You survived!"
+    } else {
+        "You died!"
+    };
+    let score = match &app.solution {
+        Some(solution) => format!(
+            "Optimal was {}, you scored {}{}",
+            solution.score,
+            app.engine.calculate_score(),
+            score_flag(app)
+        ),
+        None if app.is_comparing() => format!(
+            "Your score: {}{} (finding the optimal score…)",
+            app.engine.calculate_score(),
+            score_flag(app)
+        ),
+        None => format!("Your score: {}{}", app.engine.calculate_score(), score_flag(app)),
+    };
+    // daily runs can be compared with everyone who played the same day
+    let share = match app.daily {
+        Some(date) => format!("\n Share your result: {}\n", share_result(date, &app.engine)),
+        None => String::new(),
+    };
+    let message = Paragraph::new(Text::styled(
+        format!(
+            "{}\n {}\n Seed: {}\n{}\n {}",
+            outcome,
+            score,
+            app.engine.seed(),
+            share,
+            END_OF_GAME_INSTRUCTIONS
+        ),
+        app.theme.outcome(),
+    ))
+    .wrap(Wrap { trim: false });
+    frame.render_widget(message, layout[0]);
+
+    let Some(bot_run) = &app.bot_run else {
+        if app.is_comparing() {
+            let computing = Paragraph::new(" Computing… the greedy bot is playing the same seed.").block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .title_bottom("Creature"),
+                    .title("You vs the greedy bot, on the same seed"),
             );
-
-            frame.render_widget(weapon, chunks[0]);
-            frame.render_widget(creature, chunks[1]);
+            frame.render_widget(computing, layout[1]);
         }
+        return;
+    };
 
-        CurrentScreen::Won => {
-            let won_message = Paragraph::new(Text::styled(
-                format!("You survived!\n Your score: {}", app.calculate_score()),
-                Style::default().fg(Color::Green),
-            ));
+    // the player's run and the bot's run side by side
+    let describe_run = |engine: &Engine| {
+        let stats = engine.stats();
+        [
+            if engine.phase() == Phase::Won {
+                "Survived".to_string()
+            } else {
+                "Died".to_string()
+            },
+            engine.calculate_score().to_string(),
+            stats.rooms_cleared.to_string(),
+            stats.damage_taken.to_string(),
+            stats.potions_used.to_string(),
+        ]
+    };
+    let player = describe_run(&app.engine);
+    let bot = describe_run(bot_run);
+    let rows = ["Result", "Score", "Rooms cleared", "Damage taken", "Potions used"]
+        .into_iter()
+        .zip(player.into_iter().zip(bot))
+        .map(|(label, (player, bot))| Row::new(vec![label.to_string(), player, bot]));
+    let comparison = Table::new(rows, [Length(16), Length(12), Length(12)])
+        .header(
+            Row::new(vec!["", "You", "Greedy bot"])
+                .style(Style::default().add_modifier(Modifier::BOLD)),
+        )
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("You vs the greedy bot, on the same seed"),
+        );
+    frame.render_widget(comparison, layout[1]);
 
-            frame.render_widget(won_message, frame.area());
-        }
+    let moves: Vec<ListItem> = app
+        .bot_moves
+        .iter()
+        .skip(app.bot_moves_scroll)
+        .map(|description| ListItem::new(description.as_str()))
+        .collect();
+    let moves = List::new(moves).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("The greedy bot's moves ({})", app.bot_moves.len()))
+            .title_bottom("scroll (up/down)"),
+    );
+    frame.render_widget(moves, layout[2]);
+}
 
-        CurrentScreen::Lost => {
-            let lost_message = Paragraph::new(Text::styled(
-                format!("You died!\n Your score: {}", app.calculate_score()),
-                Style::default().fg(Color::Green),
-            ));
+/// Flags whether the score was achieved with the help of undo.
+fn score_flag(app: &App) -> &'static str {
+    if app.daily.is_some() {
+        " (daily challenge)"
+    } else if !app.run_settings.allow_undo {
+        " (ironman)"
+    } else if app.has_used_undo {
+        " (undo used)"
+    } else {
+        ""
+    }
+}
 
-            frame.render_widget(lost_message, frame.area());
-        }
+/// Reminds the player of the keys that can be used during a run - undo and redo are left out if undo is disabled.
+fn controls(app: &App) -> String {
+    // hints would make the daily challenge unfair
+    let hints = if app.daily.is_some() {
+        "no hints in the daily challenge"
+    } else if app.show_hints {
+        "hide hints (h)"
+    } else {
+        "show hints (h)"
+    };
 
-        _ => {}
+    if app.run_settings.allow_undo {
+        format!("{hints}, undo (u) / redo (r), rules (?)")
+    } else {
+        format!("{hints}, rules (?)")
     }
 }
 
-fn render_number_of_cards_left(frame: &mut Frame, app: &App, area: Rect) {
-    let number_of_cards_cleared = MAX_DECK_SIZE - app.deck.len();
-    let percentage_cleared = (number_of_cards_cleared as f64 / MAX_DECK_SIZE as f64) * 100.0;
+fn render_number_of_cards_left(frame: &mut Frame, engine: &Engine, area: Rect) {
+    let deck_size = engine.deck().size();
+    let number_of_cards_cleared = deck_size - engine.deck().len();
+    let percentage_cleared = (number_of_cards_cleared as f64 / deck_size as f64) * 100.0;
+
+    // cards from avoided rooms are at the bottom of the deck, so the player knows when they will come up again
+    let known_cards_at_bottom = engine
+        .deck()
+        .known_cards_at_bottom()
+        .map(|card| format!("{} {}", card, card.get_emoji()))
+        .collect::<Vec<_>>();
+    // the dungeon of the tutorial only holds some of the cards of its variant
+    let variant = engine.deck().variant();
+    let deck = if deck_size == variant.size() {
+        variant.to_string()
+    } else {
+        format!("{deck_size} cards")
+    };
+    let mut block = Block::default().title(format!(
+        "Progress (seed: {}, deck: {})",
+        engine.seed(),
+        deck
+    ));
+    if !known_cards_at_bottom.is_empty() {
+        block = block.title_bottom(format!(
+            "Bottom of the dungeon: {}",
+            known_cards_at_bottom.join(", ")
+        ));
+    }
 
     let gauge = Gauge::default()
         .gauge_style(tailwind::BLUE.c800)
         .percent(percentage_cleared.round() as u16)
-        .block(Block::default().title("Progress"));
+        .block(block);
 
     frame.render_widget(gauge, area);
 }
 
-fn render_notifications(frame: &mut Frame, app: &App, area: Rect) {
-    let notification = if let Some(notification) = app.notifications.last() {
+/// Renders a grid of every card, showing which were used, which are in the room and which remain in the
+/// dungeon, above the discard pile - most recent card first.
+fn render_cards_seen(frame: &mut Frame, theme: Theme, engine: &Engine, area: Rect) {
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![Length(Suite::iter().len() as u16 + 3), Length(5), Fill(1)])
+        .split(area);
+
+    let header = Row::new(
+        std::iter::once(String::new())
+            .chain(Rank::iter().map(|rank| rank_label(rank).to_string())),
+    )
+    .style(Style::default().add_modifier(Modifier::BOLD));
+    let rows = Suite::iter().map(|suite| {
+        let cells = Rank::iter().map(|rank| {
+            let card = Card { suite, rank };
+            let style = match engine.card_status(&card) {
+                CardStatus::Used => theme.disabled().add_modifier(Modifier::CROSSED_OUT),
+                CardStatus::InRoom => theme.card(card.suite),
+                CardStatus::Remaining => Style::default().add_modifier(Modifier::BOLD),
+                CardStatus::NotInDungeon => return Cell::from(""),
+            };
+            Cell::from(rank_label(rank)).style(style)
+        });
+        Row::new(std::iter::once(Cell::from(suite_symbol(suite))).chain(cells))
+    });
+    let grid = Table::new(rows, [Length(2); 14]).header(header).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Cards seen")
+            .title_bottom("used / in room / remaining"),
+    );
+    frame.render_widget(grid, layout[0]);
+    render_dungeon_stats(frame, engine, layout[1]);
+
+    let discards: Vec<ListItem> = engine
+        .discard_pile()
+        .iter()
+        .rev()
+        .map(|discard| {
+            ListItem::new(format!(
+                "{}{} {} {}",
+                suite_symbol(discard.card.suite),
+                rank_label(discard.card.rank),
+                discard.card.get_emoji(),
+                discard.resolution
+            ))
+        })
+        .collect();
+    let discard_pile = List::new(discards).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Discard pile ({})", engine.discard_pile().len())),
+    );
+    frame.render_widget(discard_pile, layout[2]);
+}
+
+/// Renders what is left in the dungeon, to judge the risk of the rooms ahead.
+fn render_dungeon_stats(frame: &mut Frame, engine: &Engine, area: Rect) {
+    let stats = engine.dungeon_stats();
+    let label = |card: Card| format!("{}{}", suite_symbol(card.suite), rank_label(card.rank));
+
+    let strongest_monster = match stats.strongest_monster {
+        Some(monster) => format!(", strongest {}", label(monster.into())),
+        None => String::new(),
+    };
+    let weapons = match stats.weapons_left.as_slice() {
+        [] => "none".to_string(),
+        weapons => weapons
+            .iter()
+            .map(|weapon| label((*weapon).into()))
+            .collect::<Vec<_>>()
+            .join(" "),
+    };
+    let lines = vec![
+        Line::from(format!(
+            "Monsters: {}, strength {}{}",
+            stats.monsters_left, stats.total_monster_strength, strongest_monster
+        )),
+        Line::from(format!(
+            "Potions: {}, healing {}",
+            stats.potions_left, stats.total_healing
+        )),
+        Line::from(format!("Weapons: {weapons}")),
+    ];
+
+    let panel = Paragraph::new(Text::from(lines)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Left in the dungeon"),
+    );
+    frame.render_widget(panel, area);
+}
+
+fn rank_label(rank: Rank) -> &'static str {
+    match rank {
+        Rank::Two => "2",
+        Rank::Three => "3",
+        Rank::Four => "4",
+        Rank::Five => "5",
+        Rank::Six => "6",
+        Rank::Seven => "7",
+        Rank::Eight => "8",
+        Rank::Nine => "9",
+        Rank::Ten => "10",
+        Rank::Jack => "J",
+        Rank::Queen => "Q",
+        Rank::King => "K",
+        Rank::Ace => "A",
+    }
+}
+
+fn suite_symbol(suite: Suite) -> &'static str {
+    match suite {
+        Suite::Spade => "♠",
+        Suite::Club => "♣",
+        Suite::Diamond => "♦",
+        Suite::Heart => "♥",
+    }
+}
+
+fn render_notifications(frame: &mut Frame, theme: Theme, message: Option<&str>, area: Rect) {
+    let notification = if let Some(notification) = message {
         Paragraph::new(Text::styled(
             notification,
-            Style::default().fg(Color::LightBlue),
+            theme.notification(),
         ))
     } else {
-        Paragraph::new(Text::styled("", Style::default().fg(Color::LightBlue)))
+        Paragraph::new(Text::styled("", theme.notification()))
     };
 
     frame.render_widget(notification, area);
 }
 
-fn render_equipped_weapon(frame: &mut Frame, app: &App, area: Rect) {
-    let equipped_weapon_key = Span::styled("Equipped weapon: ", Style::default().fg(Color::White));
-    let equipped_weapon_value = match app.equipped_weapon.as_ref() {
+fn render_equipped_weapon(frame: &mut Frame, theme: Theme, engine: &Engine, area: Rect) {
+    let equipped_weapon_key = Span::styled("Equipped weapon: ", Style::default());
+    let equipped_weapon_value = match engine.equipped_weapon() {
         Some(equipped_weapon) => Span::styled(
-            format!(
-                "{} {}",
-                equipped_weapon.to_string(),
-                equipped_weapon.get_emoji()
-            ),
-            Style::default().fg(Color::Blue),
+            format!("{} {}", equipped_weapon, equipped_weapon.get_emoji()),
+            theme.game_info(),
         ),
-        None => Span::styled("NO WEAPON EQUIPPED", Style::default().fg(Color::Blue)),
+        None => Span::styled("NO WEAPON EQUIPPED", theme.game_info()),
     };
     let equipped_weapon_line = Line::from(vec![equipped_weapon_key, equipped_weapon_value]);
 
     let last_creature_blocked_key =
-        Span::styled("Last creature blocked: ", Style::default().fg(Color::White));
-    let last_creature_blocked_value = match app.blocked_creatures.last() {
+        Span::styled("Last creature blocked: ", Style::default());
+    let last_creature_blocked_value = match engine.blocked_creatures().last() {
         Some(blocked_creature) => Span::styled(
-            format!(
-                "{} {}",
-                blocked_creature.to_string(),
-                blocked_creature.get_emoji()
-            ),
-            Style::default().fg(Color::Blue),
+            format!("{} {}", blocked_creature, blocked_creature.get_emoji()),
+            theme.game_info(),
         ),
-        None => Span::styled("NO CREATURE BLOCKED", Style::default().fg(Color::Blue)),
+        None => Span::styled("NO CREATURE BLOCKED", theme.game_info()),
     };
     let last_creature_blocked_line =
         Line::from(vec![last_creature_blocked_key, last_creature_blocked_value]);
@@ -249,62 +1116,72 @@ fn render_equipped_weapon(frame: &mut Frame, app: &App, area: Rect) {
     );
 }
 
-fn render_health(frame: &mut Frame, app: &App, area: Rect) {
+fn render_health(frame: &mut Frame, theme: Theme, engine: &Engine, area: Rect) {
+    // only the first potion used in a room heals
+    let title = if engine.potions_used_in_room() > 0 {
+        "Health (potion already used in this room)"
+    } else {
+        "Health"
+    };
+
     let health_gauge = Gauge::default()
         .gauge_style(tailwind::GREEN.c800)
-        .ratio(app.life as f64 / MAX_LIFE as f64)
+        .ratio(engine.life() as f64 / MAX_LIFE as f64)
         .label(Span::styled(
-            format!("{} / {}", app.life, MAX_LIFE),
-            Style::default().fg(Color::White),
+            format!("{} / {}", engine.life(), MAX_LIFE),
+            theme.gauge_label(),
         ))
-        .block(Block::default().borders(Borders::ALL).title("Health"));
+        .block(Block::default().borders(Borders::ALL).title(title));
 
     frame.render_widget(health_gauge, area);
 }
 
-impl ListStyle for Card {
-    fn get_list_style(&self) -> Style {
-        match self.suite {
-            Suite::Spade | Suite::Club => Style::default().fg(Color::White).bg(Color::Red),
-            Suite::Diamond => Style::default().fg(Color::White).bg(Color::Magenta),
-            Suite::Heart => Style::default().fg(Color::White).bg(Color::Green),
-        }
-    }
-}
-
-impl Emoji for Card {
-    fn get_emoji(&self) -> String {
-        match self.suite {
-            Suite::Spade | Suite::Club => "👺".to_string(),
-            Suite::Diamond => "⚔️".to_string(),
-            Suite::Heart => "❤️".to_string(),
-        }
-    }
-}
-
-pub fn render_cards(frame: &mut Frame, app: &App, area: Rect) {
+fn render_cards(frame: &mut Frame, theme: Theme, engine: &Engine, room_panel: RoomPanel, area: Rect) {
     let border = Block::new()
         .borders(Borders::ALL)
-        .title(Line::from("Room - select card (1/2/3/4)").centered());
+        .title(Line::from(room_panel.title.to_string()).centered());
+    let border = match room_panel.controls {
+        Some(controls) => border.title_bottom(Line::from(controls.to_string()).centered()),
+        None => border,
+    };
+
+    let carried_over_card_num = engine.hand().carried_over_card_num();
 
-    let list_items: Vec<ListItem> = app
-        .hand
+    let list_items: Vec<ListItem> = engine
+        .hand()
         .iter()
         .enumerate()
-        .map(|(idx, card)| match card {
-            Some(card) => ListItem::new(Text::styled(
-                format!("[{}]: {} {}", idx + 1, card.to_string(), card.get_emoji()),
-                card.get_list_style(),
-            )),
-            None => ListItem::new(Text::styled(
-                "USED",
-                Style::default().fg(Color::Black).bg(Gray),
-            )),
+        .map(|(idx, card)| {
+            let card_num = idx + 1;
+            let Some(card) = card else {
+                return ListItem::new(Text::styled("USED", theme.used_card()));
+            };
+            let mut text = format!("[{}]: {} {}", card_num, card, card.get_emoji());
+            let mut style = theme.card(card.suite);
+
+            // highlight the card that was left over from the previous room
+            if carried_over_card_num == Some(card_num) {
+                text.push_str(" (from previous room)");
+                style = style.add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+            }
+
+            if let Some(hint) = room_panel.hint {
+                let action = Action::PickCard(card_num);
+                if let Some(outcome) = hint.outcome_of(action) {
+                    text.push_str(&format!(" - {outcome}"));
+                }
+                if hint.recommended == action {
+                    text.push_str(" <- recommended");
+                    style = style.add_modifier(Modifier::REVERSED);
+                }
+            }
+
+            ListItem::new(Text::styled(text, style))
         })
         .collect();
 
     let list_widget = List::new(list_items).block(border);
-    frame.render_widget(&list_widget, centered_rect(50, 80, area));
+    frame.render_widget(&list_widget, centered_rect(70, 80, area));
 }
 
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {